@@ -12,10 +12,15 @@ use std::process::Command;
 fn main() {
     println!("cargo::rustc-check-cfg=cfg(no_asm)");
     println!("cargo:rerun-if-changed=asm/");
-    
+
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
     let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
-    
+
+    // Cargo sets this for build scripts to the *target* OS, which is what
+    // decides NASM's object format - not the host running the build.
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let (nasm_format, format_define) = nasm_format_for(&target_os);
+
     // Find NASM executable
     let nasm_path = match find_nasm() {
         Some(path) => {
@@ -67,10 +72,13 @@ fn main() {
         return;
     }
     
-    // Compile with nasm-rs
+    // Compile with nasm-rs, targeting whichever object format the target
+    // triple needs so the `.asm` sources can guard symbol decoration and
+    // calling-convention differences with `#ifdef WIN64`/`ELF`/`MACHO`.
     let mut build = nasm_rs::Build::new();
-    build.target("win64");
-    
+    build.target(nasm_format);
+    build.define(format_define, None);
+
     for file in &existing_files {
         build.file(file);
     }
@@ -89,7 +97,24 @@ fn main() {
     }
 }
 
-/// Find NASM executable on the system
+/// Map a `CARGO_CFG_TARGET_OS` value to the NASM output format and the
+/// preprocessor define the `.asm` sources use to tell targets apart. Unknown
+/// target OSes fall back to `elf64`/`ELF`, the most common non-Windows case.
+fn nasm_format_for(target_os: &str) -> (&'static str, &'static str) {
+    match target_os {
+        "windows" => ("win64", "WIN64"),
+        "macos" | "ios" => ("macho64", "MACHO"),
+        _ => ("elf64", "ELF"),
+    }
+}
+
+/// Find NASM executable on the system.
+///
+/// Host-agnostic: checks `NASM` first, then `PATH`, then a handful of common
+/// install locations for whichever platform is actually running the build
+/// (XDG-style local-bin dirs on Linux, Homebrew prefixes on macOS, the usual
+/// installer dirs on Windows), then shells out to `which`/`where` as a last
+/// resort.
 fn find_nasm() -> Option<String> {
     // 1. Check NASM environment variable
     if let Ok(nasm) = env::var("NASM") {
@@ -98,61 +123,77 @@ fn find_nasm() -> Option<String> {
             return Some(nasm);
         }
     }
-    
-    // 2. Check known Windows locations
-    let known_paths = [
-        // User-specific locations
-        r"C:\Users\andre\AppData\Local\bin\NASM\nasm.exe",
-        r"C:\Users\andre\AppData\Local\NASM\nasm.exe",
-        r"C:\Users\andre\NASM\nasm.exe",
-        // System-wide locations
-        r"C:\NASM\nasm.exe",
-        r"C:\Program Files\NASM\nasm.exe",
-        r"C:\Program Files (x86)\NASM\nasm.exe",
-        // Chocolatey
-        r"C:\ProgramData\chocolatey\bin\nasm.exe",
-        // Scoop
-        r"C:\Users\andre\scoop\shims\nasm.exe",
-    ];
-    
-    for path in &known_paths {
-        let p = PathBuf::from(path);
-        if p.exists() && p.is_file() {
-            return Some(path.to_string());
+
+    let nasm_exe = if cfg!(windows) { "nasm.exe" } else { "nasm" };
+
+    // 2. Search in PATH
+    if let Some(path_var) = env::var_os("PATH") {
+        for dir in env::split_paths(&path_var) {
+            let candidate = dir.join(nasm_exe);
+            if candidate.exists() && candidate.is_file() {
+                return Some(candidate.to_string_lossy().to_string());
+            }
         }
     }
-    
-    // 3. Search in PATH
-    if let Ok(path_var) = env::var("PATH") {
-        let separator = if cfg!(windows) { ';' } else { ':' };
-        for dir in path_var.split(separator) {
-            let nasm_exe = if cfg!(windows) { "nasm.exe" } else { "nasm" };
-            let nasm_path = PathBuf::from(dir).join(nasm_exe);
-            if nasm_path.exists() && nasm_path.is_file() {
-                return Some(nasm_path.to_string_lossy().to_string());
-            }
+
+    // 3. Common install locations PATH sometimes misses, per platform.
+    for dir in common_install_dirs() {
+        let candidate = PathBuf::from(dir).join(nasm_exe);
+        if candidate.exists() && candidate.is_file() {
+            return Some(candidate.to_string_lossy().to_string());
         }
     }
-    
-    // 4. Try 'where' command on Windows
-    #[cfg(windows)]
-    {
-        if let Ok(output) = Command::new("where").arg("nasm").output() {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                if let Some(first_line) = stdout.lines().next() {
-                    let path = first_line.trim();
-                    if !path.is_empty() && PathBuf::from(path).exists() {
-                        return Some(path.to_string());
-                    }
+
+    // 4. Shell out to the platform's "where is this on PATH" tool.
+    let finder = if cfg!(windows) { "where" } else { "which" };
+    if let Ok(output) = Command::new(finder).arg("nasm").output() {
+        if output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if let Some(first_line) = stdout.lines().next() {
+                let path = first_line.trim();
+                if !path.is_empty() && PathBuf::from(path).exists() {
+                    return Some(path.to_string());
                 }
             }
         }
     }
-    
+
     None
 }
 
+/// Platform-specific directories worth a direct check beyond `PATH` - XDG
+/// user/local-bin conventions on Linux, Homebrew's prefixes on macOS, and the
+/// usual package-manager install dirs on Windows.
+fn common_install_dirs() -> Vec<String> {
+    if cfg!(target_os = "windows") {
+        let mut dirs = vec![
+            r"C:\NASM".to_string(),
+            r"C:\Program Files\NASM".to_string(),
+            r"C:\Program Files (x86)\NASM".to_string(),
+            r"C:\ProgramData\chocolatey\bin".to_string(),
+        ];
+        if let Ok(profile) = env::var("USERPROFILE") {
+            dirs.push(format!(r"{profile}\scoop\shims"));
+            dirs.push(format!(r"{profile}\AppData\Local\NASM"));
+        }
+        dirs
+    } else if cfg!(target_os = "macos") {
+        vec![
+            "/opt/homebrew/bin".to_string(),
+            "/usr/local/bin".to_string(),
+        ]
+    } else {
+        let mut dirs = vec!["/usr/local/bin".to_string(), "/usr/bin".to_string()];
+        if let Ok(home) = env::var("HOME") {
+            dirs.push(format!("{home}/.local/bin"));
+        }
+        if let Ok(xdg_bin) = env::var("XDG_BIN_HOME") {
+            dirs.push(xdg_bin);
+        }
+        dirs
+    }
+}
+
 /// Verify NASM executable works correctly
 fn verify_nasm(nasm_path: &str) -> bool {
     match Command::new(nasm_path).arg("-v").output() {
@@ -5,7 +5,8 @@
 
 use engine::{
     EngineConfig,
-    math::{Vec2, FixedPoint},
+    math::{Vec2, FixedPoint, XorShift},
+    physics::{Bounds, PhysicalEntity},
     render::{Renderer, colors},
     input::{InputState, Key},
     core::{GameLoop, Timer},
@@ -15,6 +16,166 @@ const GROUND_Y: i32 = 500;
 const GRAVITY: FixedPoint = FixedPoint::from_raw(0x0000_6000); // ~0.375
 const WALK_SPEED: FixedPoint = FixedPoint::from_raw(0x0003_0000); // 3.0
 const JUMP_FORCE: FixedPoint = FixedPoint::from_raw(-786432); // -12.0 in 16.16 fixed point
+const FIREBALL_SPEED: FixedPoint = FixedPoint::from_raw(0x0006_0000); // 6.0
+const FIREBALL_LIFETIME: u16 = 90;
+const FIREBALL_DAMAGE: u8 = 8;
+const FIREBALL_KNOCKBACK: i32 = 4;
+
+/// Which fighter owns a projectile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayerId {
+    P1,
+    P2,
+}
+
+/// One fighter's input for a single frame. Directions are held state; buttons
+/// are edge-triggered (true only on the frame they are pressed) so recorded
+/// inputs replay identically during rollback.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FighterInput {
+    pub left: bool,
+    pub right: bool,
+    pub up: bool,
+    pub down: bool,
+    pub jump: bool,
+    pub attack: bool,
+    pub fireball: bool,
+}
+
+/// Number of confirmed frames kept for rollback re-simulation.
+const ROLLBACK_FRAMES: usize = 8;
+
+/// Frames of directional/button history kept for motion matching.
+const INPUT_BUFFER_LEN: usize = 20;
+
+/// Max zero-duration control-flow ops (`CANCEL`/`GOTO`) resolved inline, per
+/// fixed frame, before a move script must be considered stuck. Move scripts
+/// are modder-authored text files (see [`MoveScript::parse`]), so a `GOTO`
+/// cycle that never reaches a `STARTUP`/`ACTIVE`/`RECOVERY` op is an authoring
+/// mistake this engine needs to survive rather than trust away.
+const MAX_SCRIPT_STEPS: u32 = 64;
+
+/// One recorded frame of facing-relative directional + button state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct InputFrame {
+    /// Numpad direction (5 = neutral), already mirrored to face the opponent.
+    dir: u8,
+    attack_pressed: bool,
+    attack_released: bool,
+}
+
+/// Rolling buffer of recent inputs used to recognize command motions. Part of
+/// the fighter snapshot so rollback re-simulation matches motions identically.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputBuffer {
+    frames: std::collections::VecDeque<InputFrame>,
+    prev_attack: bool,
+}
+
+impl InputBuffer {
+    /// Record a frame. Directions are stored facing-relative so a forward
+    /// motion is always `6` regardless of which way the fighter faces.
+    fn record(&mut self, input: FighterInput, facing_right: bool) {
+        let (mut h, v) = (0i8, if input.up { 1 } else if input.down { -1 } else { 0 });
+        if input.left {
+            h -= 1;
+        }
+        if input.right {
+            h += 1;
+        }
+        // Flip horizontal so "forward" always reads as right (+1).
+        if !facing_right {
+            h = -h;
+        }
+        let dir = numpad(h, v);
+
+        let attack_pressed = input.attack && !self.prev_attack;
+        let attack_released = !input.attack && self.prev_attack;
+        self.prev_attack = input.attack;
+
+        if self.frames.len() == INPUT_BUFFER_LEN {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(InputFrame { dir, attack_pressed, attack_released });
+    }
+
+    /// Do the given facing-relative directions appear in order within the last
+    /// `window` recorded frames?
+    fn has_motion(&self, motion: &[u8], window: usize) -> bool {
+        let start = self.frames.len().saturating_sub(window);
+        let mut step = 0;
+        for frame in self.frames.iter().skip(start) {
+            if step < motion.len() && frame.dir == motion[step] {
+                step += 1;
+            }
+        }
+        step == motion.len()
+    }
+
+    /// Was attack pressed or released in the last `n` frames (negative edge)?
+    fn attack_edge(&self, n: usize) -> bool {
+        let start = self.frames.len().saturating_sub(n);
+        self.frames
+            .iter()
+            .skip(start)
+            .any(|f| f.attack_pressed || f.attack_released)
+    }
+
+    /// Count forward (`6`) taps separated by neutral in the last `window`
+    /// frames, for double-tap dash detection.
+    fn forward_taps(&self, window: usize) -> u8 {
+        let start = self.frames.len().saturating_sub(window);
+        let mut taps = 0;
+        let mut armed = true;
+        for frame in self.frames.iter().skip(start) {
+            if frame.dir == 6 && armed {
+                taps += 1;
+                armed = false;
+            } else if frame.dir == 5 {
+                armed = true;
+            }
+        }
+        taps
+    }
+
+    /// Recognize a completed command motion, consuming nothing.
+    fn detect(&self) -> Option<SpecialMove> {
+        // Dragon punch (623) takes precedence over fireball (236).
+        if self.has_motion(&[6, 2, 3], 12) && self.attack_edge(4) {
+            Some(SpecialMove::DragonPunch)
+        } else if self.has_motion(&[2, 3, 6], 12) && self.attack_edge(4) {
+            Some(SpecialMove::Fireball)
+        } else if self.forward_taps(8) >= 2 {
+            Some(SpecialMove::Dash)
+        } else {
+            None
+        }
+    }
+}
+
+/// A recognized command motion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecialMove {
+    Fireball,
+    DragonPunch,
+    Dash,
+}
+
+/// Map a horizontal/vertical sign pair to numpad notation.
+fn numpad(h: i8, v: i8) -> u8 {
+    match (h, v) {
+        (0, 0) => 5,
+        (-1, 0) => 4,
+        (1, 0) => 6,
+        (0, 1) => 8,
+        (0, -1) => 2,
+        (-1, 1) => 7,
+        (1, 1) => 9,
+        (-1, -1) => 1,
+        (1, -1) => 3,
+        _ => 5,
+    }
+}
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FighterState {
@@ -22,11 +183,64 @@ pub enum FighterState {
     Walking,
     Jumping,
     Attacking,
+    SpecialAttack,
     Hitstun,
     Blockstun,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// A short-lived animated impact effect ("caret"), spawned on hits and
+/// landings to give visual feedback for the frame-data the simulation already
+/// computes. Kept in `FixedPoint` and part of the rollback snapshot so
+/// re-simulation reproduces identical effects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EffectKind {
+    HitSpark,
+    BlockSpark,
+    Dust,
+}
+
+impl EffectKind {
+    /// How many frames the effect lives.
+    fn lifetime(self) -> u8 {
+        match self {
+            EffectKind::HitSpark => 10,
+            EffectKind::BlockSpark => 12,
+            EffectKind::Dust => 14,
+        }
+    }
+
+    /// Base ARGB colour for the effect.
+    fn color(self) -> u32 {
+        match self {
+            EffectKind::HitSpark => 0xFFFFFF40,
+            EffectKind::BlockSpark => 0xFF40C0FF,
+            EffectKind::Dust => 0xFFB0B0B0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Effect {
+    pub x: FixedPoint,
+    pub y: FixedPoint,
+    pub frame: u8,
+    pub lifetime: u8,
+    pub kind: EffectKind,
+}
+
+impl Effect {
+    fn new(x: FixedPoint, y: FixedPoint, kind: EffectKind) -> Self {
+        Self { x, y, frame: 0, lifetime: kind.lifetime(), kind }
+    }
+
+    /// Advance one frame. Returns `false` once the effect has expired.
+    fn update(&mut self) -> bool {
+        self.frame = self.frame.saturating_add(1);
+        self.frame < self.lifetime
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Hitbox {
     pub x: FixedPoint,
     pub y: FixedPoint,
@@ -46,11 +260,19 @@ impl Hitbox {
         }
     }
     
+    /// Centre point of the box, used to place impact effects.
+    pub fn center(&self) -> (FixedPoint, FixedPoint) {
+        (
+            self.x + FixedPoint::from_raw(self.width.raw() / 2),
+            self.y + FixedPoint::from_raw(self.height.raw() / 2),
+        )
+    }
+
     pub fn intersects(&self, other: &Hitbox) -> bool {
         if !self.active || !other.active {
             return false;
         }
-        
+
         let self_right = self.x + self.width;
         let self_bottom = self.y + self.height;
         let other_right = other.x + other.width;
@@ -61,7 +283,243 @@ impl Hitbox {
     }
 }
 
-#[derive(Clone)]
+/// One instruction in a compiled [`MoveScript`]. Moves are pure data: a
+/// fighter's attack is just a `Vec<MoveOp>` stepped one op per fixed update,
+/// so new characters and moves ship as text files instead of hardcoded frame
+/// windows in [`Fighter::update`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveOp {
+    /// Hold for `frames` with no hitbox active.
+    Startup { frames: u16 },
+    /// Hold for `frames` with a hitbox active at `(dx, dy, w, h)` relative to
+    /// the fighter (mirrored when facing left), dealing `damage` and knocking
+    /// back by `knockback` (same integer scale as [`WALK_SPEED`]) on hit.
+    Active {
+        frames: u16,
+        dx: i32,
+        dy: i32,
+        w: i32,
+        h: i32,
+        damage: u8,
+        knockback: i32,
+    },
+    /// Hold for `frames` with no hitbox, then end the move.
+    Recovery { frames: u16 },
+    /// If attack is pressed on the frame execution reaches here, switch into
+    /// the named move immediately (a combo cancel); otherwise fall through.
+    ///
+    /// Owned rather than `&'static str`: a byte-serialized snapshot round
+    /// trip decodes a fresh `MoveOp` every call (potentially every rollback
+    /// resync), and leaking a new `'static` string per decode would never be
+    /// reclaimed.
+    Cancel { into: Box<str> },
+    /// Unconditionally switch into the named move, or return to `Idle` if the
+    /// name is `"idle"`. See [`Cancel`](MoveOp::Cancel) for why this is owned.
+    Goto { label: Box<str> },
+}
+
+/// Errors surfaced while parsing a [`MoveScript`].
+#[derive(Debug)]
+pub enum MoveScriptError {
+    Io(std::io::Error),
+    /// `(line number, message)` - 1-indexed to match what an editor shows.
+    Parse(usize, String),
+}
+
+impl From<std::io::Error> for MoveScriptError {
+    fn from(e: std::io::Error) -> Self {
+        MoveScriptError::Io(e)
+    }
+}
+
+/// A compiled, named move - an ordered list of [`MoveOp`]s.
+///
+/// Parsed from a line-based text format, one instruction per line:
+///
+/// ```text
+/// STARTUP 7
+/// ACTIVE 3 HITBOX 30 -60 40 20 DMG 8 KNOCKBACK 5
+/// RECOVERY 12
+/// CANCEL punch
+/// GOTO idle
+/// ```
+///
+/// Blank lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MoveScript {
+    pub ops: Vec<MoveOp>,
+}
+
+impl MoveScript {
+    /// Load and parse a move script from a text file.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, MoveScriptError> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text)
+    }
+
+    /// Parse a move script from its in-memory text form.
+    pub fn parse(text: &str) -> Result<Self, MoveScriptError> {
+        let mut ops = Vec::new();
+        for (i, raw_line) in text.lines().enumerate() {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let lineno = i + 1;
+            let op = match tokens.as_slice() {
+                ["STARTUP", frames] => MoveOp::Startup {
+                    frames: parse_u16(frames, lineno)?,
+                },
+                ["RECOVERY", frames] => MoveOp::Recovery {
+                    frames: parse_u16(frames, lineno)?,
+                },
+                ["CANCEL", into] => MoveOp::Cancel { into: Box::from(*into) },
+                ["GOTO", label] => MoveOp::Goto { label: Box::from(*label) },
+                ["ACTIVE", frames, "HITBOX", dx, dy, w, h, "DMG", damage, "KNOCKBACK", knockback] => {
+                    MoveOp::Active {
+                        frames: parse_u16(frames, lineno)?,
+                        dx: parse_i32(dx, lineno)?,
+                        dy: parse_i32(dy, lineno)?,
+                        w: parse_i32(w, lineno)?,
+                        h: parse_i32(h, lineno)?,
+                        damage: parse_u16(damage, lineno)? as u8,
+                        knockback: parse_i32(knockback, lineno)?,
+                    }
+                }
+                _ => {
+                    return Err(MoveScriptError::Parse(
+                        lineno,
+                        format!("unrecognized instruction: `{line}`"),
+                    ))
+                }
+            };
+            ops.push(op);
+        }
+        Ok(Self { ops })
+    }
+}
+
+fn parse_u16(token: &str, lineno: usize) -> Result<u16, MoveScriptError> {
+    token
+        .parse()
+        .map_err(|_| MoveScriptError::Parse(lineno, format!("expected an integer, got `{token}`")))
+}
+
+fn parse_i32(token: &str, lineno: usize) -> Result<i32, MoveScriptError> {
+    token
+        .parse()
+        .map_err(|_| MoveScriptError::Parse(lineno, format!("expected an integer, got `{token}`")))
+}
+
+/// Built-in move set, keyed by name, shared by both fighters. Swapping this
+/// for a loader that walks a `moves/` directory of `.txt` scripts is all a mod
+/// needs to add new characters.
+pub type MoveLibrary = std::collections::HashMap<&'static str, MoveScript>;
+
+/// Compile the engine's default "punch" and "special" moves. These mirror the
+/// timings the hand-coded state machine used to hardcode, just expressed as
+/// data instead of `if state_frame >= N` checks.
+pub fn default_move_library() -> MoveLibrary {
+    const PUNCH: &str = "\
+STARTUP 3
+ACTIVE 4 HITBOX 30 -60 40 20 DMG 10 KNOCKBACK 5
+CANCEL special
+RECOVERY 8
+GOTO idle
+";
+    const SPECIAL: &str = "\
+STARTUP 4
+ACTIVE 8 HITBOX 30 -60 40 20 DMG 16 KNOCKBACK 7
+RECOVERY 13
+GOTO idle
+";
+
+    let mut library = MoveLibrary::new();
+    library.insert("punch", MoveScript::parse(PUNCH).expect("built-in move script is valid"));
+    library.insert("special", MoveScript::parse(SPECIAL).expect("built-in move script is valid"));
+    library
+}
+
+/// A deterministic projectile (fireball/bullet) owned by a fighter.
+#[derive(Clone, PartialEq)]
+pub struct Projectile {
+    pub x: FixedPoint,
+    pub y: FixedPoint,
+    pub vel_x: FixedPoint,
+    pub vel_y: FixedPoint,
+    pub lifetime: u16,
+    pub damage: u8,
+    pub owner: PlayerId,
+    pub facing_right: bool,
+    pub hitbox: Hitbox,
+    /// Per-projectile RNG, seeded from the owning fighter, for deterministic
+    /// variance that survives rollback.
+    pub rng: XorShift,
+}
+
+impl Projectile {
+    /// Advance one fixed step. Returns `false` once the projectile is spent.
+    pub fn update(&mut self) -> bool {
+        self.apply_physics();
+        self.lifetime = self.lifetime.saturating_sub(1);
+
+        // Track the hitbox to the projectile body.
+        self.hitbox.x = self.x - FixedPoint::from_int(10);
+        self.hitbox.y = self.y - FixedPoint::from_int(10);
+        self.hitbox.active = self.lifetime > 0;
+        self.lifetime > 0
+    }
+}
+
+impl PhysicalEntity for Projectile {
+    fn x(&self) -> FixedPoint { self.x }
+    fn y(&self) -> FixedPoint { self.y }
+    fn vel_x(&self) -> FixedPoint { self.vel_x }
+    fn vel_y(&self) -> FixedPoint { self.vel_y }
+
+    fn set_x(&mut self, x: FixedPoint) { self.x = x; }
+    fn set_y(&mut self, y: FixedPoint) { self.y = y; }
+    fn set_vel_x(&mut self, vel_x: FixedPoint) { self.vel_x = vel_x; }
+    fn set_vel_y(&mut self, vel_y: FixedPoint) { self.vel_y = vel_y; }
+
+    // Fireballs fly in a straight line: no gravity, no ground collision.
+    fn hit_bounds(&self) -> Bounds {
+        Bounds::new(
+            self.x - FixedPoint::from_int(10),
+            self.y - FixedPoint::from_int(10),
+            FixedPoint::from_int(20),
+            FixedPoint::from_int(20),
+        )
+    }
+
+    fn display_bounds(&self) -> Bounds {
+        self.hit_bounds()
+    }
+}
+
+/// Owns all live projectiles and ticks them each fixed update.
+#[derive(Default)]
+pub struct ProjectileManager {
+    pub projectiles: Vec<Projectile>,
+}
+
+impl ProjectileManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn(&mut self, projectile: Projectile) {
+        self.projectiles.push(projectile);
+    }
+
+    /// Advance all projectiles, dropping the dead ones.
+    pub fn update(&mut self) {
+        self.projectiles.retain_mut(|p| p.update());
+    }
+}
+
+#[derive(Clone, PartialEq)]
 pub struct Fighter {
     pub x: FixedPoint,
     pub y: FixedPoint,
@@ -73,11 +531,29 @@ pub struct Fighter {
     pub health: u8,
     pub hitbox: Hitbox,
     pub hurtbox: Hitbox,
+    /// Per-fighter RNG, seeded at creation from the game seeder, so randomized
+    /// knockback/variance reproduces exactly under rollback.
+    pub rng: XorShift,
     pub grounded: bool,
+    /// Per-direction collision flags resolved against the stage each frame.
+    pub touching_left: bool,
+    pub touching_right: bool,
+    pub touching_ground: bool,
+    pub touching_ceiling: bool,
+    pub input_buffer: InputBuffer,
+    /// The move script currently driving `Attacking`/`SpecialAttack`, and
+    /// where execution is inside it. Empty outside those two states.
+    pub script: Vec<MoveOp>,
+    pub script_pc: usize,
+    pub script_timer: u16,
+    /// Damage/knockback carried by the hitbox the active script op just
+    /// opened, read by the hit-resolution step in `FightingGame::fixed_update`.
+    pub attack_damage: u8,
+    pub attack_knockback: i32,
 }
 
 impl Fighter {
-    pub fn new(x: i32, facing_right: bool) -> Self {
+    pub fn new(x: i32, facing_right: bool, seed: u32) -> Self {
         Self {
             x: FixedPoint::from_int(x),
             y: FixedPoint::from_int(GROUND_Y),
@@ -89,60 +565,45 @@ impl Fighter {
             health: 100,
             hitbox: Hitbox::new(0, 0, 40, 20),
             hurtbox: Hitbox::new(0, 0, 50, 100),
+            rng: XorShift::new(seed),
             grounded: true,
+            touching_left: false,
+            touching_right: false,
+            touching_ground: true,
+            touching_ceiling: false,
+            input_buffer: InputBuffer::default(),
+            script: Vec::new(),
+            script_pc: 0,
+            script_timer: 0,
+            attack_damage: 0,
+            attack_knockback: 0,
         }
     }
     
-    pub fn update(&mut self) {
-        // Apply gravity
-        if !self.grounded {
-            self.vel_y = self.vel_y + GRAVITY;
-        }
-        
-        // Apply velocity
-        self.x = self.x + self.vel_x;
-        self.y = self.y + self.vel_y;
-        
-        // Ground check
-        let ground = FixedPoint::from_int(GROUND_Y);
-        if self.y >= ground {
-            self.y = ground;
-            self.vel_y = FixedPoint::ZERO;
-            self.grounded = true;
-            
-            if self.state == FighterState::Jumping {
-                self.state = FighterState::Idle;
-                self.state_frame = 0;
-            }
-        }
-        
+    /// Advance one fixed frame. `library` resolves the move names a script's
+    /// `CANCEL`/`GOTO` ops reference; `cancel_requested` is this frame's
+    /// attack input (edge-triggered, see [`FighterInput`]), i.e. whether a
+    /// `CANCEL` op in flight should take its branch this frame.
+    pub fn update(&mut self, library: &MoveLibrary, cancel_requested: bool) {
+        // Velocity integration and gravity are shared with every other moving
+        // object via the engine trait; ground/wall resolution is handled by the
+        // stage in `tick_map_collisions` after this call.
+        self.apply_physics();
+
         // Update hurtbox position
         self.hurtbox.x = self.x - FixedPoint::from_int(25);
         self.hurtbox.y = self.y - FixedPoint::from_int(100);
         self.hurtbox.active = true;
-        
+
         // Update state
         self.state_frame = self.state_frame.saturating_add(1);
-        
-        // Handle attack frames
-        if self.state == FighterState::Attacking {
-            // Active frames 3-6
-            if self.state_frame >= 3 && self.state_frame <= 6 {
-                self.hitbox.active = true;
-                let offset = if self.facing_right { 30 } else { -70 };
-                self.hitbox.x = self.x + FixedPoint::from_int(offset);
-                self.hitbox.y = self.y - FixedPoint::from_int(60);
-            } else {
-                self.hitbox.active = false;
-            }
-            
-            // Recovery at frame 15
-            if self.state_frame >= 15 {
-                self.state = FighterState::Idle;
-                self.state_frame = 0;
-            }
+
+        // Attacking/SpecialAttack are both just "a move script is running";
+        // the script itself carries the startup/active/recovery timing.
+        if self.state == FighterState::Attacking || self.state == FighterState::SpecialAttack {
+            self.step_move_script(library, cancel_requested);
         }
-        
+
         // Handle hitstun
         if self.state == FighterState::Hitstun {
             if self.state_frame >= 20 {
@@ -151,7 +612,130 @@ impl Fighter {
             }
         }
     }
-    
+
+    /// Begin running `script` (a move by name, resolved by the caller). Resets
+    /// the program counter so the first call to `step_move_script` executes
+    /// the script's first op. Takes `library` (rather than gating the first
+    /// op's `GOTO`/`CANCEL` resolution on its absence) so a script that opens
+    /// with a control-flow op jumps correctly on its very first step.
+    fn start_move(&mut self, library: &MoveLibrary, state: FighterState, script: MoveScript) {
+        self.state = state;
+        self.state_frame = 0;
+        self.vel_x = FixedPoint::ZERO;
+        self.script = script.ops;
+        self.script_pc = usize::MAX; // wraps to 0 on the first advance
+        self.script_timer = 0;
+        self.hitbox.active = false;
+        self.step_move_script_ops(library, false); // land on op 0 immediately
+    }
+
+    /// End the currently running move and return to `Idle`.
+    fn end_move(&mut self) {
+        self.state = FighterState::Idle;
+        self.state_frame = 0;
+        self.hitbox.active = false;
+        self.script.clear();
+        self.script_pc = 0;
+        self.script_timer = 0;
+    }
+
+    /// Step the active script by one fixed frame, resolving `CANCEL`/`GOTO`
+    /// against `library`.
+    fn step_move_script(&mut self, library: &MoveLibrary, cancel_requested: bool) {
+        if self.script_timer > 0 {
+            self.script_timer -= 1;
+            if self.script_timer > 0 {
+                self.sync_active_hitbox();
+                return;
+            }
+        }
+        self.step_move_script_ops(library, cancel_requested);
+    }
+
+    /// Advance the program counter past the current (expired) op, resolving
+    /// zero-duration control-flow ops (`CANCEL`/`GOTO`) inline - including ones
+    /// that jump into a brand-new script - until a frame-consuming op
+    /// (`STARTUP`/`ACTIVE`/`RECOVERY`) is reached or the script ends. Bails out
+    /// to `Idle` after [`MAX_SCRIPT_STEPS`] zero-duration steps instead of
+    /// looping forever, so a modder-authored `GOTO`/`CANCEL` cycle that never
+    /// reaches a frame-consuming op ends the move cleanly rather than hanging
+    /// the sim.
+    fn step_move_script_ops(&mut self, library: &MoveLibrary, cancel_requested: bool) {
+        for _ in 0..MAX_SCRIPT_STEPS {
+            self.script_pc = self.script_pc.wrapping_add(1);
+            let Some(op) = self.script.get(self.script_pc).cloned() else {
+                self.end_move();
+                return;
+            };
+            match op {
+                MoveOp::Startup { frames } => {
+                    self.hitbox.active = false;
+                    self.script_timer = frames.max(1);
+                    return;
+                }
+                MoveOp::Active { frames, dx, dy, w, h, damage, knockback } => {
+                    self.hitbox.active = true;
+                    self.attack_damage = damage;
+                    self.attack_knockback = knockback;
+                    self.script_timer = frames.max(1);
+                    self.position_hitbox(dx, dy, w, h);
+                    return;
+                }
+                MoveOp::Recovery { frames } => {
+                    self.hitbox.active = false;
+                    self.script_timer = frames.max(1);
+                    return;
+                }
+                MoveOp::Cancel { into } => {
+                    if cancel_requested {
+                        if let Some(next) = library.get(into.as_ref()) {
+                            self.script = next.ops.clone();
+                            self.script_pc = usize::MAX; // wraps to 0 below
+                            continue;
+                        }
+                    }
+                    // Cancel window closed (or the move is unknown): fall
+                    // through to whatever comes next in the same script.
+                }
+                MoveOp::Goto { label } => {
+                    if &*label == "idle" {
+                        self.end_move();
+                        return;
+                    }
+                    let Some(next) = library.get(label.as_ref()) else {
+                        self.end_move();
+                        return;
+                    };
+                    self.script = next.ops.clone();
+                    self.script_pc = usize::MAX; // wraps to 0 below
+                }
+            }
+        }
+        // Exceeded the zero-duration step budget - almost certainly an
+        // authored GOTO/CANCEL cycle. End the move rather than spin forever.
+        self.end_move();
+    }
+
+    /// Place the active-op hitbox at the fighter's current position, mirrored
+    /// across the facing direction the same way the old hand-coded offsets
+    /// were (`-70 == -(30 + 40)` for a 40-wide box at offset 30).
+    fn position_hitbox(&mut self, dx: i32, dy: i32, w: i32, h: i32) {
+        let (dx, w) = if self.facing_right { (dx, w) } else { (-dx - w, w) };
+        self.hitbox.x = self.x + FixedPoint::from_int(dx);
+        self.hitbox.y = self.y + FixedPoint::from_int(dy);
+        self.hitbox.width = FixedPoint::from_int(w);
+        self.hitbox.height = FixedPoint::from_int(h);
+    }
+
+    /// Re-track an already-active hitbox to the fighter's current position
+    /// between script steps (the fighter doesn't move mid-attack, but this
+    /// keeps the invariant explicit rather than assumed).
+    fn sync_active_hitbox(&mut self) {
+        if let Some(MoveOp::Active { dx, dy, w, h, .. }) = self.script.get(self.script_pc) {
+            self.position_hitbox(*dx, *dy, *w, *h);
+        }
+    }
+
     pub fn walk(&mut self, direction: i32) {
         if self.state != FighterState::Idle && self.state != FighterState::Walking {
             return;
@@ -185,29 +769,294 @@ impl Fighter {
         self.grounded = false;
     }
     
-    pub fn attack(&mut self) {
+    /// Enter the "punch" move script, driving `Attacking`'s timing from data
+    /// instead of hardcoded frame checks.
+    pub fn attack(&mut self, library: &MoveLibrary) {
         if self.state == FighterState::Attacking || !self.grounded {
             return;
         }
-        
-        self.state = FighterState::Attacking;
-        self.state_frame = 0;
-        self.vel_x = FixedPoint::ZERO;
+        if let Some(script) = library.get("punch") {
+            self.start_move(library, FighterState::Attacking, script.clone());
+        }
     }
-    
-    pub fn take_hit(&mut self, damage: u8) {
+
+    /// Enter the "special" move script (longer startup/active window than a
+    /// normal punch).
+    pub fn special_attack(&mut self, library: &MoveLibrary) {
+        if self.state == FighterState::Attacking
+            || self.state == FighterState::SpecialAttack
+            || !self.grounded
+        {
+            return;
+        }
+        if let Some(script) = library.get("special") {
+            self.start_move(library, FighterState::SpecialAttack, script.clone());
+        }
+    }
+
+    /// Burst forward in the facing direction (double-tap dash).
+    pub fn dash(&mut self) {
+        if self.state != FighterState::Idle && self.state != FighterState::Walking {
+            return;
+        }
+        let dash = WALK_SPEED + WALK_SPEED;
+        self.vel_x = if self.facing_right { dash } else { FixedPoint::ZERO - dash };
+        self.state = FighterState::Walking;
+    }
+
+    /// Spawn a projectile travelling horizontally in the facing direction,
+    /// offset from the fighter's upper body.
+    pub fn fireball(&mut self, owner: PlayerId) -> Projectile {
+        let offset = if self.facing_right { 35 } else { -35 };
+        let vel_x = if self.facing_right {
+            FIREBALL_SPEED
+        } else {
+            FixedPoint::ZERO - FIREBALL_SPEED
+        };
+        let x = self.x + FixedPoint::from_int(offset);
+        let y = self.y - FixedPoint::from_int(60);
+
+        let mut hitbox = Hitbox::new(0, 0, 20, 20);
+        hitbox.active = true;
+        Projectile {
+            x,
+            y,
+            vel_x,
+            vel_y: FixedPoint::ZERO,
+            lifetime: FIREBALL_LIFETIME,
+            damage: FIREBALL_DAMAGE,
+            owner,
+            facing_right: self.facing_right,
+            hitbox,
+            rng: XorShift::new(self.rng.next_u32()),
+        }
+    }
+
+    /// Apply a hit of `damage`, knocked back at `base_knockback` (small integer
+    /// units, the same scale as [`WALK_SPEED`]) — the value a move's `ACTIVE`
+    /// op carries, so each move can push differently.
+    pub fn take_hit(&mut self, damage: u8, base_knockback: i32) {
         self.health = self.health.saturating_sub(damage);
         self.state = FighterState::Hitstun;
         self.state_frame = 0;
         self.hitbox.active = false;
-        
-        // Knockback
-        let knockback = if self.facing_right {
-            FixedPoint::from_int(-5)
+
+        // Knockback, jittered by ±1 so repeated hits are not identical. The
+        // RNG is part of the snapshot, so the jitter reproduces under rollback.
+        let jitter = self.rng.range(0..3) as i32 - 1; // -1, 0, or +1
+        let magnitude = base_knockback + jitter;
+        self.vel_x = if self.facing_right {
+            FixedPoint::from_int(-magnitude)
         } else {
-            FixedPoint::from_int(5)
+            FixedPoint::from_int(magnitude)
         };
-        self.vel_x = knockback;
+    }
+}
+
+impl PhysicalEntity for Fighter {
+    fn x(&self) -> FixedPoint { self.x }
+    fn y(&self) -> FixedPoint { self.y }
+    fn vel_x(&self) -> FixedPoint { self.vel_x }
+    fn vel_y(&self) -> FixedPoint { self.vel_y }
+
+    fn set_x(&mut self, x: FixedPoint) { self.x = x; }
+    fn set_y(&mut self, y: FixedPoint) { self.y = y; }
+    fn set_vel_x(&mut self, vel_x: FixedPoint) { self.vel_x = vel_x; }
+    fn set_vel_y(&mut self, vel_y: FixedPoint) { self.vel_y = vel_y; }
+
+    fn gravity(&self) -> FixedPoint { GRAVITY }
+
+    // Ground resolution is tile-driven (see `Stage::tick_map_collisions`), so
+    // the trait performs velocity integration and gravity only.
+    fn grounded(&self) -> bool { self.grounded }
+    fn set_grounded(&mut self, grounded: bool) { self.grounded = grounded; }
+
+    fn hit_bounds(&self) -> Bounds {
+        Bounds::new(
+            self.x - FixedPoint::from_int(25),
+            self.y - FixedPoint::from_int(100),
+            FixedPoint::from_int(50),
+            FixedPoint::from_int(100),
+        )
+    }
+
+    fn display_bounds(&self) -> Bounds {
+        self.hit_bounds()
+    }
+}
+
+/// Edge length of a single stage tile, in world units.
+const TILE_SIZE: i32 = 50;
+
+/// What a single stage tile collides as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileKind {
+    /// No collision.
+    Empty,
+    /// Blocks movement from every direction.
+    Solid,
+    /// Blocks only a descending entity's feet; passable from below or while
+    /// holding down (drop-through).
+    OneWay,
+}
+
+/// A tile-grid stage. Replaces the single `GROUND_Y` plane with walls,
+/// platforms, and a floor so fighters collide against real geometry. The grid
+/// never mutates during a match, so it lives outside the rollback snapshot.
+#[derive(Clone, PartialEq)]
+pub struct Stage {
+    pub cols: usize,
+    pub rows: usize,
+    tiles: Vec<TileKind>,
+}
+
+impl Stage {
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            tiles: vec![TileKind::Empty; cols * rows],
+        }
+    }
+
+    /// Build the default arena for a screen of the given size: a solid floor
+    /// level with `GROUND_Y`, solid side walls, and a one-way platform fighters
+    /// can jump onto and drop through.
+    pub fn arena(width: u32, height: u32) -> Self {
+        let cols = (width as i32 / TILE_SIZE) as usize;
+        let rows = (height as i32 / TILE_SIZE) as usize;
+        let mut stage = Self::new(cols, rows);
+
+        let floor_row = (GROUND_Y / TILE_SIZE) as usize;
+        for row in floor_row..rows {
+            for col in 0..cols {
+                stage.set(col, row, TileKind::Solid);
+            }
+        }
+        for row in 0..rows {
+            stage.set(0, row, TileKind::Solid);
+            stage.set(cols - 1, row, TileKind::Solid);
+        }
+
+        // A one-way platform roughly mid-arena.
+        if rows >= 7 && cols >= 8 {
+            let platform_row = floor_row.saturating_sub(3);
+            for col in (cols / 2 - 2)..(cols / 2 + 2) {
+                stage.set(col, platform_row, TileKind::OneWay);
+            }
+        }
+
+        stage
+    }
+
+    fn set(&mut self, col: usize, row: usize, kind: TileKind) {
+        if col < self.cols && row < self.rows {
+            self.tiles[row * self.cols + col] = kind;
+        }
+    }
+
+    /// Tile at a grid coordinate. Out-of-bounds sides and floor read as solid so
+    /// fighters cannot walk off-screen; above the grid reads as empty.
+    pub fn tile(&self, col: i32, row: i32) -> TileKind {
+        if row < 0 {
+            return TileKind::Empty;
+        }
+        if col < 0 || col as usize >= self.cols || row as usize >= self.rows {
+            return TileKind::Solid;
+        }
+        self.tiles[row as usize * self.cols + col as usize]
+    }
+
+    /// Resolve a fighter against the tile grid and set its per-direction
+    /// collision flags. Modeled on the external engine's `tick_map_collisions`:
+    /// vertical first (so landing is stable), then horizontal wall push-out.
+    pub fn tick_map_collisions(&self, fighter: &mut Fighter, drop_through: bool) {
+        const HALF_W: i32 = 25;
+        const HEIGHT: i32 = 100;
+
+        fighter.touching_left = false;
+        fighter.touching_right = false;
+        fighter.touching_ground = false;
+        fighter.touching_ceiling = false;
+
+        // --- Vertical ---
+        let left_col = (fighter.x.to_int() - HALF_W) / TILE_SIZE;
+        let right_col = (fighter.x.to_int() + HALF_W - 1) / TILE_SIZE;
+
+        if fighter.vel_y.raw() >= 0 {
+            // Descending (or resting): check the row under the feet.
+            let feet = fighter.y.to_int();
+            let row = feet / TILE_SIZE;
+            let tile_top = row * TILE_SIZE;
+            let mut landed = false;
+            for col in left_col..=right_col {
+                match self.tile(col, row) {
+                    TileKind::Solid => landed = true,
+                    TileKind::OneWay => {
+                        // Only land on the surface, and never while dropping
+                        // through.
+                        let crossing = feet - tile_top <= fighter.vel_y.to_int() + 1;
+                        if !drop_through && fighter.vel_y.raw() > 0 && crossing {
+                            landed = true;
+                        }
+                    }
+                    TileKind::Empty => {}
+                }
+            }
+            if landed {
+                fighter.y = FixedPoint::from_int(tile_top);
+                fighter.vel_y = FixedPoint::ZERO;
+                fighter.grounded = true;
+                fighter.touching_ground = true;
+                if fighter.state == FighterState::Jumping {
+                    fighter.state = FighterState::Idle;
+                    fighter.state_frame = 0;
+                }
+            } else {
+                fighter.grounded = false;
+            }
+        } else {
+            // Ascending: check the row at the head for a solid ceiling.
+            let head = fighter.y.to_int() - HEIGHT;
+            let row = head / TILE_SIZE;
+            let mut bumped = false;
+            for col in left_col..=right_col {
+                if self.tile(col, row) == TileKind::Solid {
+                    bumped = true;
+                }
+            }
+            if bumped {
+                fighter.y = FixedPoint::from_int((row + 1) * TILE_SIZE + HEIGHT);
+                fighter.vel_y = FixedPoint::ZERO;
+                fighter.touching_ceiling = true;
+            }
+            fighter.grounded = false;
+        }
+
+        // --- Horizontal ---
+        let top_row = (fighter.y.to_int() - HEIGHT) / TILE_SIZE;
+        let bottom_row = (fighter.y.to_int() - 1) / TILE_SIZE;
+        let right_edge = fighter.x.to_int() + HALF_W;
+        let left_edge = fighter.x.to_int() - HALF_W;
+
+        let right_wall = right_edge / TILE_SIZE;
+        let left_wall = left_edge / TILE_SIZE;
+        for row in top_row..=bottom_row {
+            if self.tile(right_wall, row) == TileKind::Solid {
+                fighter.x = FixedPoint::from_int(right_wall * TILE_SIZE - HALF_W);
+                if fighter.vel_x.raw() > 0 {
+                    fighter.vel_x = FixedPoint::ZERO;
+                }
+                fighter.touching_right = true;
+            }
+            if self.tile(left_wall, row) == TileKind::Solid {
+                fighter.x = FixedPoint::from_int((left_wall + 1) * TILE_SIZE + HALF_W);
+                if fighter.vel_x.raw() < 0 {
+                    fighter.vel_x = FixedPoint::ZERO;
+                }
+                fighter.touching_left = true;
+            }
+        }
     }
 }
 
@@ -218,27 +1067,675 @@ pub struct FightingGame {
     game_loop: GameLoop,
     player1: Fighter,
     player2: Fighter,
+    projectiles: ProjectileManager,
+    stage: Stage,
+    /// Live impact effects (hit sparks, dust); part of the snapshot so they
+    /// replay identically under rollback.
+    effects: Vec<Effect>,
+    /// Master RNG that seeds each fighter (and, through them, each projectile).
+    seeder: XorShift,
+    /// Compiled move scripts shared by both fighters; swapping this for a
+    /// directory loader is all a mod needs to add new moves/characters.
+    moves: MoveLibrary,
     frame: u64,
+    /// Ring buffer of recent snapshots + the inputs applied that frame, for
+    /// GGPO-style rollback re-simulation.
+    history: std::collections::VecDeque<(u64, GameState, (FighterInput, FighterInput))>,
+    /// Per-frame recording of the whole match, for CSV export and replay.
+    pub telemetry: Telemetry,
     running: bool,
 }
 
-impl FightingGame {
-    pub fn new(config: EngineConfig) -> Self {
+/// A compact clone of the entire simulation state for rollback.
+#[derive(Clone, PartialEq)]
+pub struct GameState {
+    pub player1: Fighter,
+    pub player2: Fighter,
+    pub projectiles: Vec<Projectile>,
+    pub effects: Vec<Effect>,
+    pub frame: u64,
+}
+
+// --- Byte-serialized snapshots -------------------------------------------
+//
+// A `GameState` is already a cheap value clone for in-memory rollback, but a
+// netcode layer or a save file needs a flat `Vec<u8>` it can put on the wire
+// or on disk. These are hand-rolled (little-endian, one field at a time, in
+// struct-declaration order) rather than pulled in from a serde-style crate,
+// matching the rest of this file's explicit field-by-field style (see
+// `FrameRecord::to_csv_row`).
+
+fn push_u8(buf: &mut Vec<u8>, v: u8) {
+    buf.push(v);
+}
+
+fn push_bool(buf: &mut Vec<u8>, v: bool) {
+    buf.push(v as u8);
+}
+
+fn push_u16(buf: &mut Vec<u8>, v: u16) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn push_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn push_i32(buf: &mut Vec<u8>, v: i32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn push_u64(buf: &mut Vec<u8>, v: u64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn push_fixed(buf: &mut Vec<u8>, v: FixedPoint) {
+    push_i32(buf, v.raw());
+}
+
+fn push_str(buf: &mut Vec<u8>, s: &str) {
+    push_u16(buf, s.len() as u16);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// Cursor over a byte slice produced by the `push_*` helpers above. Every
+/// `read_*` returns `None` on truncation instead of panicking, so a corrupt or
+/// foreign buffer fails [`GameState::from_bytes`] cleanly.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos + n)?;
+        self.pos += n;
+        Some(slice)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn read_bool(&mut self) -> Option<bool> {
+        Some(self.read_u8()? != 0)
+    }
+
+    fn read_u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes(self.take(2)?.try_into().ok()?))
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn read_i32(&mut self) -> Option<i32> {
+        Some(i32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn read_u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    fn read_fixed(&mut self) -> Option<FixedPoint> {
+        Some(FixedPoint::from_raw(self.read_i32()?))
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        let len = self.read_u16()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+}
+
+impl PlayerId {
+    fn to_byte(self) -> u8 {
+        match self {
+            PlayerId::P1 => 0,
+            PlayerId::P2 => 1,
+        }
+    }
+
+    fn from_byte(b: u8) -> Option<Self> {
+        Some(match b {
+            0 => PlayerId::P1,
+            1 => PlayerId::P2,
+            _ => return None,
+        })
+    }
+}
+
+impl FighterState {
+    fn to_byte(self) -> u8 {
+        match self {
+            FighterState::Idle => 0,
+            FighterState::Walking => 1,
+            FighterState::Jumping => 2,
+            FighterState::Attacking => 3,
+            FighterState::SpecialAttack => 4,
+            FighterState::Hitstun => 5,
+            FighterState::Blockstun => 6,
+        }
+    }
+
+    fn from_byte(b: u8) -> Option<Self> {
+        Some(match b {
+            0 => FighterState::Idle,
+            1 => FighterState::Walking,
+            2 => FighterState::Jumping,
+            3 => FighterState::Attacking,
+            4 => FighterState::SpecialAttack,
+            5 => FighterState::Hitstun,
+            6 => FighterState::Blockstun,
+            _ => return None,
+        })
+    }
+}
+
+impl EffectKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            EffectKind::HitSpark => 0,
+            EffectKind::BlockSpark => 1,
+            EffectKind::Dust => 2,
+        }
+    }
+
+    fn from_byte(b: u8) -> Option<Self> {
+        Some(match b {
+            0 => EffectKind::HitSpark,
+            1 => EffectKind::BlockSpark,
+            2 => EffectKind::Dust,
+            _ => return None,
+        })
+    }
+}
+
+impl Hitbox {
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        push_fixed(buf, self.x);
+        push_fixed(buf, self.y);
+        push_fixed(buf, self.width);
+        push_fixed(buf, self.height);
+        push_bool(buf, self.active);
+    }
+
+    fn read_from(r: &mut ByteReader) -> Option<Self> {
+        Some(Self {
+            x: r.read_fixed()?,
+            y: r.read_fixed()?,
+            width: r.read_fixed()?,
+            height: r.read_fixed()?,
+            active: r.read_bool()?,
+        })
+    }
+}
+
+impl InputFrame {
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        push_u8(buf, self.dir);
+        push_bool(buf, self.attack_pressed);
+        push_bool(buf, self.attack_released);
+    }
+
+    fn read_from(r: &mut ByteReader) -> Option<Self> {
+        Some(Self {
+            dir: r.read_u8()?,
+            attack_pressed: r.read_bool()?,
+            attack_released: r.read_bool()?,
+        })
+    }
+}
+
+impl InputBuffer {
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        push_u16(buf, self.frames.len() as u16);
+        for frame in &self.frames {
+            frame.write_to(buf);
+        }
+        push_bool(buf, self.prev_attack);
+    }
+
+    fn read_from(r: &mut ByteReader) -> Option<Self> {
+        let len = r.read_u16()? as usize;
+        let mut frames = std::collections::VecDeque::with_capacity(len);
+        for _ in 0..len {
+            frames.push_back(InputFrame::read_from(r)?);
+        }
+        Some(Self { frames, prev_attack: r.read_bool()? })
+    }
+}
+
+impl MoveOp {
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        match self.clone() {
+            MoveOp::Startup { frames } => {
+                push_u8(buf, 0);
+                push_u16(buf, frames);
+            }
+            MoveOp::Active { frames, dx, dy, w, h, damage, knockback } => {
+                push_u8(buf, 1);
+                push_u16(buf, frames);
+                push_i32(buf, dx);
+                push_i32(buf, dy);
+                push_i32(buf, w);
+                push_i32(buf, h);
+                push_u8(buf, damage);
+                push_i32(buf, knockback);
+            }
+            MoveOp::Recovery { frames } => {
+                push_u8(buf, 2);
+                push_u16(buf, frames);
+            }
+            MoveOp::Cancel { into } => {
+                push_u8(buf, 3);
+                push_str(buf, &into);
+            }
+            MoveOp::Goto { label } => {
+                push_u8(buf, 4);
+                push_str(buf, &label);
+            }
+        }
+    }
+
+    fn read_from(r: &mut ByteReader) -> Option<Self> {
+        Some(match r.read_u8()? {
+            0 => MoveOp::Startup { frames: r.read_u16()? },
+            1 => MoveOp::Active {
+                frames: r.read_u16()?,
+                dx: r.read_i32()?,
+                dy: r.read_i32()?,
+                w: r.read_i32()?,
+                h: r.read_i32()?,
+                damage: r.read_u8()?,
+                knockback: r.read_i32()?,
+            },
+            2 => MoveOp::Recovery { frames: r.read_u16()? },
+            3 => MoveOp::Cancel { into: r.read_string()?.into_boxed_str() },
+            4 => MoveOp::Goto { label: r.read_string()?.into_boxed_str() },
+            _ => return None,
+        })
+    }
+}
+
+impl Fighter {
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        push_fixed(buf, self.x);
+        push_fixed(buf, self.y);
+        push_fixed(buf, self.vel_x);
+        push_fixed(buf, self.vel_y);
+        push_bool(buf, self.facing_right);
+        push_u8(buf, self.state.to_byte());
+        push_u8(buf, self.state_frame);
+        push_u8(buf, self.health);
+        self.hitbox.write_to(buf);
+        self.hurtbox.write_to(buf);
+        push_u32(buf, self.rng.state());
+        push_bool(buf, self.grounded);
+        push_bool(buf, self.touching_left);
+        push_bool(buf, self.touching_right);
+        push_bool(buf, self.touching_ground);
+        push_bool(buf, self.touching_ceiling);
+        self.input_buffer.write_to(buf);
+        push_u16(buf, self.script.len() as u16);
+        for op in &self.script {
+            op.write_to(buf);
+        }
+        push_u32(buf, self.script_pc as u32);
+        push_u16(buf, self.script_timer);
+        push_u8(buf, self.attack_damage);
+        push_i32(buf, self.attack_knockback);
+    }
+
+    fn read_from(r: &mut ByteReader) -> Option<Self> {
+        let x = r.read_fixed()?;
+        let y = r.read_fixed()?;
+        let vel_x = r.read_fixed()?;
+        let vel_y = r.read_fixed()?;
+        let facing_right = r.read_bool()?;
+        let state = FighterState::from_byte(r.read_u8()?)?;
+        let state_frame = r.read_u8()?;
+        let health = r.read_u8()?;
+        let hitbox = Hitbox::read_from(r)?;
+        let hurtbox = Hitbox::read_from(r)?;
+        let rng = XorShift::from_state(r.read_u32()?);
+        let grounded = r.read_bool()?;
+        let touching_left = r.read_bool()?;
+        let touching_right = r.read_bool()?;
+        let touching_ground = r.read_bool()?;
+        let touching_ceiling = r.read_bool()?;
+        let input_buffer = InputBuffer::read_from(r)?;
+        let script_len = r.read_u16()? as usize;
+        let mut script = Vec::with_capacity(script_len);
+        for _ in 0..script_len {
+            script.push(MoveOp::read_from(r)?);
+        }
+        let script_pc = r.read_u32()? as usize;
+        let script_timer = r.read_u16()?;
+        let attack_damage = r.read_u8()?;
+        let attack_knockback = r.read_i32()?;
+        Some(Self {
+            x,
+            y,
+            vel_x,
+            vel_y,
+            facing_right,
+            state,
+            state_frame,
+            health,
+            hitbox,
+            hurtbox,
+            rng,
+            grounded,
+            touching_left,
+            touching_right,
+            touching_ground,
+            touching_ceiling,
+            input_buffer,
+            script,
+            script_pc,
+            script_timer,
+            attack_damage,
+            attack_knockback,
+        })
+    }
+}
+
+impl Projectile {
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        push_fixed(buf, self.x);
+        push_fixed(buf, self.y);
+        push_fixed(buf, self.vel_x);
+        push_fixed(buf, self.vel_y);
+        push_u16(buf, self.lifetime);
+        push_u8(buf, self.damage);
+        push_u8(buf, self.owner.to_byte());
+        push_bool(buf, self.facing_right);
+        self.hitbox.write_to(buf);
+        push_u32(buf, self.rng.state());
+    }
+
+    fn read_from(r: &mut ByteReader) -> Option<Self> {
+        Some(Self {
+            x: r.read_fixed()?,
+            y: r.read_fixed()?,
+            vel_x: r.read_fixed()?,
+            vel_y: r.read_fixed()?,
+            lifetime: r.read_u16()?,
+            damage: r.read_u8()?,
+            owner: PlayerId::from_byte(r.read_u8()?)?,
+            facing_right: r.read_bool()?,
+            hitbox: Hitbox::read_from(r)?,
+            rng: XorShift::from_state(r.read_u32()?),
+        })
+    }
+}
+
+impl Effect {
+    fn write_to(&self, buf: &mut Vec<u8>) {
+        push_fixed(buf, self.x);
+        push_fixed(buf, self.y);
+        push_u8(buf, self.frame);
+        push_u8(buf, self.lifetime);
+        push_u8(buf, self.kind.to_byte());
+    }
+
+    fn read_from(r: &mut ByteReader) -> Option<Self> {
+        Some(Self {
+            x: r.read_fixed()?,
+            y: r.read_fixed()?,
+            frame: r.read_u8()?,
+            lifetime: r.read_u8()?,
+            kind: EffectKind::from_byte(r.read_u8()?)?,
+        })
+    }
+}
+
+impl GameState {
+    /// Flatten the whole simulation state into a compact byte buffer -
+    /// everything [`FightingGame::snapshot`] captures, suitable for sending
+    /// over the wire or writing to a save file instead of kept as live Rust
+    /// values. See [`FightingGame::snapshot_bytes`]/[`restore_bytes`].
+    ///
+    /// [`restore_bytes`]: FightingGame::restore_bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.player1.write_to(&mut buf);
+        self.player2.write_to(&mut buf);
+        push_u32(&mut buf, self.projectiles.len() as u32);
+        for projectile in &self.projectiles {
+            projectile.write_to(&mut buf);
+        }
+        push_u32(&mut buf, self.effects.len() as u32);
+        for effect in &self.effects {
+            effect.write_to(&mut buf);
+        }
+        push_u64(&mut buf, self.frame);
+        buf
+    }
+
+    /// Parse a buffer produced by [`GameState::to_bytes`]. Returns `None` on
+    /// any truncation or unrecognized tag, which should only happen if
+    /// `bytes` didn't actually come from `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut r = ByteReader::new(bytes);
+        let player1 = Fighter::read_from(&mut r)?;
+        let player2 = Fighter::read_from(&mut r)?;
+        let projectile_count = r.read_u32()? as usize;
+        let mut projectiles = Vec::with_capacity(projectile_count);
+        for _ in 0..projectile_count {
+            projectiles.push(Projectile::read_from(&mut r)?);
+        }
+        let effect_count = r.read_u32()? as usize;
+        let mut effects = Vec::with_capacity(effect_count);
+        for _ in 0..effect_count {
+            effects.push(Effect::read_from(&mut r)?);
+        }
+        let frame = r.read_u64()?;
+        Some(Self { player1, player2, projectiles, effects, frame })
+    }
+}
+
+/// One recorded frame of match state, captured every [`FightingGame::advance_frame`]
+/// call. Exporting a whole match to CSV doubles as a regression oracle for the
+/// move-timing logic: because the sim is fixed-point and fixed-dt, replaying
+/// the same inputs through [`FightingGame::replay_inputs`] must reproduce
+/// byte-identical rows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameRecord {
+    pub frame: u64,
+    pub p1_x: FixedPoint,
+    pub p1_y: FixedPoint,
+    pub p1_vx: FixedPoint,
+    pub p1_vy: FixedPoint,
+    pub p1_health: u8,
+    pub p1_state: FighterState,
+    pub p1_state_frame: u8,
+    pub p1_input: FighterInput,
+    pub p2_x: FixedPoint,
+    pub p2_y: FixedPoint,
+    pub p2_vx: FixedPoint,
+    pub p2_vy: FixedPoint,
+    pub p2_health: u8,
+    pub p2_state: FighterState,
+    pub p2_state_frame: u8,
+    pub p2_input: FighterInput,
+}
+
+impl FrameRecord {
+    fn capture(
+        frame: u64,
+        player1: &Fighter,
+        player2: &Fighter,
+        p1_input: FighterInput,
+        p2_input: FighterInput,
+    ) -> Self {
+        Self {
+            frame,
+            p1_x: player1.x,
+            p1_y: player1.y,
+            p1_vx: player1.vel_x,
+            p1_vy: player1.vel_y,
+            p1_health: player1.health,
+            p1_state: player1.state,
+            p1_state_frame: player1.state_frame,
+            p1_input,
+            p2_x: player2.x,
+            p2_y: player2.y,
+            p2_vx: player2.vel_x,
+            p2_vy: player2.vel_y,
+            p2_health: player2.health,
+            p2_state: player2.state,
+            p2_state_frame: player2.state_frame,
+            p2_input,
+        }
+    }
+
+    /// One CSV row, matching [`Telemetry::to_csv`]'s header column order.
+    fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{:?},{},{},{},{},{},{},{},{:?},{},{}",
+            self.frame,
+            self.p1_x.to_f32(),
+            self.p1_y.to_f32(),
+            self.p1_vx.to_f32(),
+            self.p1_vy.to_f32(),
+            self.p1_health,
+            self.p1_state,
+            self.p1_state_frame,
+            input_bits(self.p1_input),
+            self.p2_x.to_f32(),
+            self.p2_y.to_f32(),
+            self.p2_vx.to_f32(),
+            self.p2_vy.to_f32(),
+            self.p2_health,
+            self.p2_state,
+            self.p2_state_frame,
+            input_bits(self.p2_input),
+        )
+    }
+}
+
+/// Pack one frame's held/pressed buttons into a single byte (`left` in bit 0
+/// through `fireball` in bit 6) so the CSV export stays one column per fighter
+/// instead of seven.
+fn input_bits(input: FighterInput) -> u8 {
+    (input.left as u8)
+        | (input.right as u8) << 1
+        | (input.up as u8) << 2
+        | (input.down as u8) << 3
+        | (input.jump as u8) << 4
+        | (input.attack as u8) << 5
+        | (input.fireball as u8) << 6
+}
+
+/// Growable per-frame recording of a match. Unlike [`FightingGame::history`]
+/// (a capped ring kept only for rollback), this keeps every frame so the whole
+/// match can be exported or plotted after the fact.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Telemetry {
+    pub frames: Vec<FrameRecord>,
+}
+
+impl Telemetry {
+    fn record(&mut self, record: FrameRecord) {
+        self.frames.push(record);
+    }
+
+    /// Render the whole recording as CSV text, header row first.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "frame,p1_x,p1_y,p1_vx,p1_vy,p1_health,p1_state,p1_state_frame,p1_input,\
+             p2_x,p2_y,p2_vx,p2_vy,p2_health,p2_state,p2_state_frame,p2_input\n",
+        );
+        for record in &self.frames {
+            out.push_str(&record.to_csv_row());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Write the recording to `path` as CSV.
+    pub fn write_csv<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_csv())
+    }
+
+    /// Render both players' health over the recording as a lightweight ASCII
+    /// chart, `width` columns by `height` rows - enough to eyeball combo
+    /// damage and hitstun windows without pulling in a plotting library.
+    /// `1`/`2` mark a column where only that player is above the row's health
+    /// threshold, `#` where both are, and a space where neither is.
+    pub fn health_plot(&self, width: usize, height: usize) -> String {
+        if self.frames.is_empty() || width == 0 || height == 0 {
+            return String::new();
+        }
+        let mut out = String::new();
+        for row in 0..height {
+            let threshold = 100i32.saturating_sub((row * 100 / height) as i32);
+            let mut line = String::with_capacity(width + 1);
+            for col in 0..width {
+                let sample = &self.frames[col * self.frames.len() / width];
+                let p1_above = sample.p1_health as i32 >= threshold;
+                let p2_above = sample.p2_health as i32 >= threshold;
+                line.push(match (p1_above, p2_above) {
+                    (true, true) => '#',
+                    (true, false) => '1',
+                    (false, true) => '2',
+                    (false, false) => ' ',
+                });
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl FightingGame {
+    pub fn new(config: EngineConfig) -> Self {
         let renderer = Renderer::new(config.width, config.height);
         let game_loop = GameLoop::new(&config);
-        
+
+        // Fixed master seed keeps each match reproducible; each fighter draws
+        // its own sub-seed so their variance streams are independent.
+        let mut seeder = XorShift::new(0x1234_5678);
+        let player1 = Fighter::new(200, true, seeder.next_u32());
+        let player2 = Fighter::new(600, false, seeder.next_u32());
+
         Self {
             config: config.clone(),
             renderer,
             input: InputState::new(),
             game_loop,
-            player1: Fighter::new(200, true),
-            player2: Fighter::new(600, false),
+            player1,
+            player2,
+            projectiles: ProjectileManager::new(),
+            stage: Stage::arena(config.width, config.height),
+            effects: Vec::new(),
+            seeder,
+            moves: default_move_library(),
             frame: 0,
+            history: std::collections::VecDeque::with_capacity(ROLLBACK_FRAMES),
+            telemetry: Telemetry::default(),
             running: true,
         }
     }
-    
+
+    /// Re-simulate a match from a recorded input stream. Because
+    /// `fixed_update` is fixed-point and fixed-dt, feeding the same
+    /// `(p1, p2)` pairs (e.g. from a loaded [`Telemetry`] CSV) through a fresh
+    /// game reproduces the original run's snapshots bit-for-bit.
+    pub fn replay_inputs(config: EngineConfig, inputs: &[(FighterInput, FighterInput)]) -> Self {
+        let mut game = Self::new(config);
+        for &(p1, p2) in inputs {
+            game.advance_frame(p1, p2);
+        }
+        game
+    }
+
     pub fn run(&mut self) {
         log::info!("Starting Fighting Game...");
         log::info!("Controls: WASD to move P1, Space to attack, Arrow keys for P2, Enter to attack");
@@ -256,17 +1753,27 @@ impl FightingGame {
                     self.input.key_pressed(Key::W);
                     self.input.key_pressed(Key::Space);
                 }
+                120 => self.input.key_pressed(Key::R),
                 _ => {}
             }
             
+            let p1 = FighterInput {
+                left: self.input.is_key_down(Key::A),
+                right: self.input.is_key_down(Key::D),
+                up: self.input.is_key_down(Key::W),
+                down: self.input.is_key_down(Key::S),
+                jump: self.input.is_key_pressed(Key::W),
+                attack: self.input.is_key_pressed(Key::Space),
+                fireball: self.input.is_key_pressed(Key::R),
+            };
+
             let tick = self.game_loop.tick();
-            
+
             for _ in 0..tick.fixed_updates {
-                self.fixed_update();
+                self.advance_frame(p1, FighterInput::default());
             }
-            
+
             self.render();
-            self.frame += 1;
             
             if frame % 60 == 0 {
                 log::info!(
@@ -282,57 +1789,318 @@ impl FightingGame {
         log::info!("Final: P1 HP={}, P2 HP={}", self.player1.health, self.player2.health);
     }
     
-    fn fixed_update(&mut self) {
-        // Handle P1 input
-        if self.input.is_key_down(Key::A) {
-            self.player1.walk(-1);
-        } else if self.input.is_key_down(Key::D) {
-            self.player1.walk(1);
+    /// Apply one fighter's input. Button presses are edge-triggered by the
+    /// caller so the same recorded sequence reproduces during rollback.
+    fn apply_input(fighter: &mut Fighter, input: FighterInput, library: &MoveLibrary) {
+        if input.left {
+            fighter.walk(-1);
+        } else if input.right {
+            fighter.walk(1);
         } else {
-            self.player1.stop();
+            fighter.stop();
         }
-        
-        if self.input.is_key_pressed(Key::W) {
-            self.player1.jump();
+        if input.jump {
+            fighter.jump();
         }
-        
-        if self.input.is_key_pressed(Key::Space) {
-            self.player1.attack();
+        if input.attack {
+            fighter.attack(library);
         }
-        
+    }
+
+    /// Act on a completed command motion. Returns a projectile to spawn for a
+    /// fireball motion, if one was recognized.
+    fn resolve_specials(
+        fighter: &mut Fighter,
+        owner: PlayerId,
+        library: &MoveLibrary,
+    ) -> Option<Projectile> {
+        match fighter.input_buffer.detect() {
+            Some(SpecialMove::Fireball) => {
+                fighter.special_attack(library);
+                Some(fighter.fireball(owner))
+            }
+            Some(SpecialMove::DragonPunch) => {
+                fighter.special_attack(library);
+                None
+            }
+            Some(SpecialMove::Dash) => {
+                fighter.dash();
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Advance the simulation one fixed step from explicit inputs. Reads no
+    /// wall-clock or unseeded RNG, so it is pure given (state, input) and safe
+    /// to replay during rollback.
+    fn fixed_update(&mut self, p1: FighterInput, p2: FighterInput) {
+        // Record into the command buffers, then recognize motions, before the
+        // per-frame movement so specials win over the basic attack.
+        self.player1.input_buffer.record(p1, self.player1.facing_right);
+        self.player2.input_buffer.record(p2, self.player2.facing_right);
+
+        Self::apply_input(&mut self.player1, p1, &self.moves);
+        Self::apply_input(&mut self.player2, p2, &self.moves);
+
+        if let Some(projectile) = Self::resolve_specials(&mut self.player1, PlayerId::P1, &self.moves) {
+            self.projectiles.spawn(projectile);
+        }
+        if let Some(projectile) = Self::resolve_specials(&mut self.player2, PlayerId::P2, &self.moves) {
+            self.projectiles.spawn(projectile);
+        }
+
+        if p1.fireball {
+            let fireball = self.player1.fireball(PlayerId::P1);
+            self.projectiles.spawn(fireball);
+        }
+        if p2.fireball {
+            let fireball = self.player2.fireball(PlayerId::P2);
+            self.projectiles.spawn(fireball);
+        }
+
         // Update fighters
-        self.player1.update();
-        self.player2.update();
-        
+        let p1_airborne = !self.player1.grounded;
+        let p2_airborne = !self.player2.grounded;
+        self.player1.update(&self.moves, p1.attack);
+        self.player2.update(&self.moves, p2.attack);
+
+        // Resolve each fighter against the stage geometry, then push the two
+        // apart so they cannot occupy the same space.
+        self.stage.tick_map_collisions(&mut self.player1, p1.down);
+        self.stage.tick_map_collisions(&mut self.player2, p2.down);
+        self.resolve_fighter_overlap();
+
+        // Dust on landing (airborne -> grounded this frame).
+        if p1_airborne && self.player1.grounded {
+            self.effects.push(Effect::new(self.player1.x, self.player1.y, EffectKind::Dust));
+        }
+        if p2_airborne && self.player2.grounded {
+            self.effects.push(Effect::new(self.player2.x, self.player2.y, EffectKind::Dust));
+        }
+
+        // Advance projectiles (ASM would accelerate this)
+        self.projectiles.update();
+
         // Check hit detection (ASM would accelerate this)
         if self.player1.hitbox.intersects(&self.player2.hurtbox) {
-            self.player2.take_hit(10);
+            Self::spawn_hit_spark(&mut self.effects, &self.player1.hitbox, &self.player2.hurtbox);
+            self.player2.take_hit(self.player1.attack_damage, self.player1.attack_knockback);
             self.player1.hitbox.active = false;
         }
-        
+
         if self.player2.hitbox.intersects(&self.player1.hurtbox) {
-            self.player1.take_hit(10);
+            Self::spawn_hit_spark(&mut self.effects, &self.player2.hitbox, &self.player1.hurtbox);
+            self.player1.take_hit(self.player2.attack_damage, self.player2.attack_knockback);
             self.player2.hitbox.active = false;
         }
+
+        // Projectiles hit the opposing fighter's hurtbox.
+        for projectile in &mut self.projectiles.projectiles {
+            let target = match projectile.owner {
+                PlayerId::P1 => &mut self.player2,
+                PlayerId::P2 => &mut self.player1,
+            };
+            if projectile.hitbox.intersects(&target.hurtbox) {
+                Self::spawn_hit_spark(&mut self.effects, &projectile.hitbox, &target.hurtbox);
+                target.take_hit(projectile.damage, FIREBALL_KNOCKBACK);
+                projectile.lifetime = 0;
+                projectile.hitbox.active = false;
+            }
+        }
+
+        // Tick and cull effects.
+        self.effects.retain_mut(|e| e.update());
+    }
+
+    /// Spawn a hit spark at the midpoint of the attacking box and the box it
+    /// overlaps.
+    fn spawn_hit_spark(effects: &mut Vec<Effect>, attack: &Hitbox, target: &Hitbox) {
+        let (ax, ay) = attack.center();
+        let (tx, ty) = target.center();
+        let x = FixedPoint::from_raw((ax.raw() + tx.raw()) / 2);
+        let y = FixedPoint::from_raw((ay.raw() + ty.raw()) / 2);
+        effects.push(Effect::new(x, y, EffectKind::HitSpark));
     }
     
+    /// Push the two fighters apart so their bodies never overlap. A fighter
+    /// already pinned against a wall (per its stage collision flags) holds its
+    /// ground and the other is pushed the full distance.
+    fn resolve_fighter_overlap(&mut self) {
+        const HALF_W: i32 = 25;
+        let dx = self.player2.x.to_int() - self.player1.x.to_int();
+        let overlap = HALF_W * 2 - dx.abs();
+        if overlap <= 0 {
+            return;
+        }
+
+        // `dir > 0` means P2 is to the right of P1.
+        let dir = if dx >= 0 { 1 } else { -1 };
+        let p1_blocked =
+            (dir > 0 && self.player1.touching_left) || (dir < 0 && self.player1.touching_right);
+        let p2_blocked =
+            (dir > 0 && self.player2.touching_right) || (dir < 0 && self.player2.touching_left);
+
+        let (p1_push, p2_push) = if p1_blocked {
+            (0, overlap)
+        } else if p2_blocked {
+            (overlap, 0)
+        } else {
+            (overlap / 2, overlap - overlap / 2)
+        };
+
+        self.player1.x = self.player1.x - FixedPoint::from_int(dir * p1_push);
+        self.player2.x = self.player2.x + FixedPoint::from_int(dir * p2_push);
+    }
+
+    /// Capture the complete simulation state.
+    pub fn snapshot(&self) -> GameState {
+        GameState {
+            player1: self.player1.clone(),
+            player2: self.player2.clone(),
+            projectiles: self.projectiles.projectiles.clone(),
+            effects: self.effects.clone(),
+            frame: self.frame,
+        }
+    }
+
+    /// Overwrite the simulation with a previously captured state.
+    pub fn restore(&mut self, state: &GameState) {
+        self.player1 = state.player1.clone();
+        self.player2 = state.player2.clone();
+        self.projectiles.projectiles = state.projectiles.clone();
+        self.effects = state.effects.clone();
+        self.frame = state.frame;
+    }
+
+    /// Advance one frame: record the pre-step snapshot + inputs, then step.
+    pub fn advance_frame(&mut self, p1: FighterInput, p2: FighterInput) {
+        let snapshot = self.snapshot();
+        if self.history.len() == ROLLBACK_FRAMES {
+            self.history.pop_front();
+        }
+        self.history.push_back((self.frame, snapshot, (p1, p2)));
+
+        self.fixed_update(p1, p2);
+        self.telemetry
+            .record(FrameRecord::capture(self.frame, &self.player1, &self.player2, p1, p2));
+        self.frame += 1;
+    }
+
+    /// Advance exactly one fixed frame from explicit inputs rather than
+    /// reading live keys - the entry point a netcode layer drives, feeding
+    /// both players' confirmed or predicted inputs by array. Thin wrapper
+    /// over [`FightingGame::advance_frame`].
+    pub fn step_with_inputs(&mut self, inputs: [FighterInput; 2]) {
+        self.advance_frame(inputs[0], inputs[1]);
+    }
+
+    /// Byte-serialized form of [`FightingGame::snapshot`], for sending a save
+    /// state over the wire or to disk instead of keeping it as live Rust
+    /// values.
+    pub fn snapshot_bytes(&self) -> Vec<u8> {
+        self.snapshot().to_bytes()
+    }
+
+    /// Restore from a buffer produced by [`FightingGame::snapshot_bytes`].
+    /// Returns `false` (leaving the game untouched) if `bytes` doesn't parse.
+    pub fn restore_bytes(&mut self, bytes: &[u8]) -> bool {
+        let Some(state) = GameState::from_bytes(bytes) else {
+            return false;
+        };
+        self.restore(&state);
+        true
+    }
+
+    /// Roll back to `frame` with a corrected remote (P2) input, then re-run the
+    /// recorded local inputs forward to the present. Because `fixed_update` is
+    /// bit-exact, re-simulation reproduces identical state. Returns `false` if
+    /// `frame` is no longer in the ring buffer.
+    pub fn rollback(&mut self, frame: u64, corrected_p2: FighterInput) -> bool {
+        let Some(start) = self.history.iter().position(|(f, ..)| *f == frame) else {
+            return false;
+        };
+
+        // Patch the corrected remote input at the diverged frame.
+        self.history[start].2 .1 = corrected_p2;
+
+        let current = self.frame;
+        let replay: Vec<(FighterInput, FighterInput)> =
+            self.history.iter().skip(start).map(|(_, _, inputs)| *inputs).collect();
+        let state = self.history[start].1.clone();
+
+        self.restore(&state);
+        self.history.truncate(start);
+        for (p1, p2) in replay {
+            self.advance_frame(p1, p2);
+        }
+        debug_assert_eq!(self.frame, current);
+        true
+    }
+
     fn render(&mut self) {
         self.renderer.clear(0xFF202020);
-        
-        // Draw ground
-        self.renderer.fill_rect(0, GROUND_Y, self.config.width, 100, 0xFF404040);
-        
+
+        // Draw the stage tiles.
+        self.draw_stage();
+
         // Draw fighters - clone to avoid borrow issues
         let p1 = self.player1.clone();
         let p2 = self.player2.clone();
         self.draw_fighter(&p1, colors::CYAN);
         self.draw_fighter(&p2, colors::RED);
         
+        // Draw projectiles
+        for projectile in &self.projectiles.projectiles {
+            let px = projectile.x.to_int();
+            let py = projectile.y.to_int();
+            self.renderer.fill_circle(px, py, 10, colors::YELLOW);
+        }
+
+        // Draw impact effects.
+        self.draw_effects();
+
         // Draw health bars
         self.draw_health_bar(50, 30, p1.health, colors::CYAN);
         self.draw_health_bar(self.config.width as i32 - 250, 30, p2.health, colors::RED);
     }
     
+    /// Draw each live effect as a quad that expands and fades over its life.
+    fn draw_effects(&mut self) {
+        for effect in &self.effects {
+            let t = effect.frame as i32;
+            let life = effect.lifetime as i32;
+            // Grows from 8 to ~24 px across its lifetime.
+            let size = 8 + (16 * t) / life;
+            // Alpha fades linearly to zero.
+            let alpha = (255 * (life - t) / life) as u32;
+            let color = (alpha << 24) | (effect.kind.color() & 0x00FF_FFFF);
+            let cx = effect.x.to_int();
+            let cy = effect.y.to_int();
+            self.renderer
+                .fill_rect(cx - size / 2, cy - size / 2, size as u32, size as u32, color);
+        }
+    }
+
+    fn draw_stage(&mut self) {
+        for row in 0..self.stage.rows {
+            for col in 0..self.stage.cols {
+                let color = match self.stage.tile(col as i32, row as i32) {
+                    TileKind::Solid => 0xFF404040,
+                    TileKind::OneWay => 0xFF606030,
+                    TileKind::Empty => continue,
+                };
+                self.renderer.fill_rect(
+                    col as i32 * TILE_SIZE,
+                    row as i32 * TILE_SIZE,
+                    TILE_SIZE as u32,
+                    TILE_SIZE as u32,
+                    color,
+                );
+            }
+        }
+    }
+
     fn draw_fighter(&mut self, fighter: &Fighter, color: u32) {
         let x = fighter.x.to_int();
         let y = fighter.y.to_int();
@@ -368,3 +2136,129 @@ impl FightingGame {
         self.renderer.fill_rect(x, y, health_width, 20, color);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A deterministic 60-frame input script for P1.
+    fn scripted_input(frame: u64) -> FighterInput {
+        FighterInput {
+            right: frame < 20,
+            left: (20..40).contains(&frame),
+            jump: frame == 10,
+            attack: frame == 25,
+            fireball: frame == 45,
+            ..FighterInput::default()
+        }
+    }
+
+    #[test]
+    fn rollback_resimulation_is_bit_exact() {
+        let config = EngineConfig::default();
+        let mut game = FightingGame::new(config);
+
+        let start = game.snapshot();
+
+        // Run 60 frames of the scripted match.
+        for frame in 0..60 {
+            game.advance_frame(scripted_input(frame), FighterInput::default());
+        }
+        let first = game.snapshot();
+
+        // Restore to the start and replay the identical inputs.
+        game.restore(&start);
+        for frame in 0..60 {
+            game.advance_frame(scripted_input(frame), FighterInput::default());
+        }
+        let second = game.snapshot();
+
+        // Fixed-point arithmetic is bit-exact, so the states must be identical.
+        assert!(first.player1 == second.player1);
+        assert!(first.player2 == second.player2);
+        assert_eq!(first.projectiles, second.projectiles);
+        assert_eq!(first.frame, second.frame);
+    }
+
+    #[test]
+    fn replay_inputs_is_bit_exact_and_telemetry_covers_every_frame() {
+        let config = EngineConfig::default();
+        let mut game = FightingGame::new(config.clone());
+
+        let inputs: Vec<(FighterInput, FighterInput)> =
+            (0..60).map(|f| (scripted_input(f), FighterInput::default())).collect();
+        for &(p1, p2) in &inputs {
+            game.advance_frame(p1, p2);
+        }
+
+        let replayed = FightingGame::replay_inputs(config, &inputs);
+
+        assert!(game.player1 == replayed.player1);
+        assert!(game.player2 == replayed.player2);
+        assert_eq!(game.telemetry.frames.len(), inputs.len());
+        assert_eq!(replayed.telemetry.frames.len(), inputs.len());
+        assert_eq!(game.telemetry.frames, replayed.telemetry.frames);
+    }
+
+    #[test]
+    fn byte_snapshot_round_trips_and_resimulates_bit_exact() {
+        let config = EngineConfig::default();
+        let mut game = FightingGame::new(config.clone());
+        for frame in 0..30 {
+            game.step_with_inputs([scripted_input(frame), FighterInput::default()]);
+        }
+
+        let bytes = game.snapshot_bytes();
+        let parsed = GameState::from_bytes(&bytes).expect("snapshot should round-trip");
+        assert!(parsed == game.snapshot());
+
+        // Keep running the source game forward, then restore a fresh game
+        // from the byte snapshot and re-step it over the same remaining
+        // inputs: `step_with_inputs`/`restore_bytes` must reproduce the
+        // source game's state bit-for-bit.
+        for frame in 30..45 {
+            game.step_with_inputs([scripted_input(frame), FighterInput::default()]);
+        }
+
+        let mut restored = FightingGame::new(config);
+        assert!(restored.restore_bytes(&bytes));
+        for frame in 30..45 {
+            restored.step_with_inputs([scripted_input(frame), FighterInput::default()]);
+        }
+
+        assert!(restored.player1 == game.player1);
+        assert!(restored.player2 == game.player2);
+        assert_eq!(restored.snapshot_bytes(), game.snapshot_bytes());
+    }
+
+    #[test]
+    fn start_move_jumps_immediately_when_the_first_op_is_goto() {
+        let mut library = MoveLibrary::new();
+        library.insert("lands_on_active", MoveScript::parse("ACTIVE 4 HITBOX 0 0 10 10 DMG 1 KNOCKBACK 0\nGOTO idle\n").unwrap());
+        library.insert("redirect", MoveScript::parse("GOTO lands_on_active\n").unwrap());
+
+        let mut fighter = Fighter::new(0, true, 1);
+        fighter.start_move(&library, FighterState::Attacking, library.get("redirect").unwrap().clone());
+
+        // A first-op GOTO must land on the target script's first frame-consuming
+        // op in the same call, not silently end the move.
+        assert_eq!(fighter.state, FighterState::Attacking);
+        assert!(fighter.hitbox.active);
+        assert_eq!(fighter.script_timer, 4);
+    }
+
+    #[test]
+    fn goto_cycle_ends_the_move_instead_of_recursing_forever() {
+        let mut library = MoveLibrary::new();
+        library.insert("ping", MoveScript::parse("GOTO pong\n").unwrap());
+        library.insert("pong", MoveScript::parse("GOTO ping\n").unwrap());
+
+        let mut fighter = Fighter::new(0, true, 1);
+        fighter.start_move(&library, FighterState::Attacking, library.get("ping").unwrap().clone());
+
+        // `MAX_SCRIPT_STEPS` caps the zero-duration GOTO/GOTO cycle, so the move
+        // cleanly ends in `Idle` rather than looping forever.
+        assert_eq!(fighter.state, FighterState::Idle);
+        assert!(!fighter.hitbox.active);
+    }
+}
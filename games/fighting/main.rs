@@ -11,6 +11,10 @@
 use std::num::NonZeroU32;
 use std::rc::Rc;
 use std::time::Instant;
+use engine::input::{
+    FighterButtons, GamepadButton as EngineGamepadButton, GamepadInput, InputState as EngineInput,
+    Key as EngineKey, KeyboardInput, MatchBindingsConfig, PlayerInput, PlayerSource,
+};
 use softbuffer::{Context, Surface};
 use winit::{
     application::ApplicationHandler,
@@ -26,6 +30,92 @@ const HEIGHT: u32 = 600;
 const GROUND_Y: i32 = 500;
 const FIXED_DT: f64 = 1.0 / 60.0;
 
+/// P1's keyboard half: WASD to move/jump, F/G to attack.
+fn p1_keyboard() -> KeyboardInput {
+    KeyboardInput {
+        left: EngineKey::A,
+        right: EngineKey::D,
+        up: EngineKey::W,
+        down: EngineKey::S,
+        jump: EngineKey::W,
+        punch: EngineKey::F,
+        kick: EngineKey::G,
+    }
+}
+
+/// P2's keyboard half: arrow keys to move/jump, K/L to attack.
+fn p2_keyboard() -> KeyboardInput {
+    KeyboardInput {
+        left: EngineKey::Left,
+        right: EngineKey::Right,
+        up: EngineKey::Up,
+        down: EngineKey::Down,
+        jump: EngineKey::Up,
+        punch: EngineKey::K,
+        kick: EngineKey::L,
+    }
+}
+
+/// Translate a winit physical key to the engine's [`EngineKey`]; only the
+/// subset this demo binds needs to round-trip.
+fn map_key(code: KeyCode) -> Option<EngineKey> {
+    Some(match code {
+        KeyCode::KeyA => EngineKey::A,
+        KeyCode::KeyD => EngineKey::D,
+        KeyCode::KeyW => EngineKey::W,
+        KeyCode::KeyS => EngineKey::S,
+        KeyCode::KeyF => EngineKey::F,
+        KeyCode::KeyG => EngineKey::G,
+        KeyCode::KeyK => EngineKey::K,
+        KeyCode::KeyL => EngineKey::L,
+        KeyCode::ArrowLeft => EngineKey::Left,
+        KeyCode::ArrowRight => EngineKey::Right,
+        KeyCode::ArrowUp => EngineKey::Up,
+        KeyCode::ArrowDown => EngineKey::Down,
+        _ => return None,
+    })
+}
+
+/// Poll one player's buttons for this frame, resolving a [`PlayerSource`]
+/// against whichever device it names: the fixed keyboard half for
+/// `Keyboard`, or the gamepad matching `guid` (first connected pad if the
+/// config didn't pin one, or the pad isn't plugged in) for `Gamepad`.
+fn poll_player(source: &PlayerSource, keyboard: KeyboardInput, input: &EngineInput) -> FighterButtons {
+    match source {
+        PlayerSource::Keyboard => keyboard.poll(input),
+        PlayerSource::Gamepad { guid } => {
+            let id = guid
+                .as_deref()
+                .and_then(|guid| input.gamepads().find_by_guid(guid))
+                .or_else(|| input.gamepads().enumerate().first().copied());
+            match id {
+                Some(id) => GamepadInput {
+                    id,
+                    jump: EngineGamepadButton::North,
+                    punch: EngineGamepadButton::South,
+                    kick: EngineGamepadButton::East,
+                }
+                .poll(input),
+                None => FighterButtons::default(),
+            }
+        }
+    }
+}
+
+/// Load per-player bindings from `fighting_bindings.toml` next to the
+/// executable, if present. Missing or unparsable files fall back to both
+/// players on the keyboard, which keeps the demo runnable without any config
+/// at all.
+#[cfg(feature = "serde")]
+fn load_bindings_config() -> MatchBindingsConfig {
+    MatchBindingsConfig::from_file("fighting_bindings.toml").unwrap_or_default()
+}
+
+#[cfg(not(feature = "serde"))]
+fn load_bindings_config() -> MatchBindingsConfig {
+    MatchBindingsConfig::default()
+}
+
 fn main() {
     env_logger::init();
     let event_loop = EventLoop::new().expect("Failed");
@@ -41,15 +131,31 @@ struct App {
     game: Game,
     last_time: Instant,
     accumulator: f64,
+    /// Raw keys plus any connected gamepads; `p1_source`/`p2_source` are
+    /// polled against this each frame to fill `game.p1.input`/`p2.input`.
+    input: EngineInput,
+    p1_source: PlayerSource,
+    p2_source: PlayerSource,
+    /// Drains `gilrs` events into `input`'s gamepad registry each frame.
+    /// Absent (and gamepads simply never connect) on builds without the
+    /// `gilrs` feature.
+    #[cfg(feature = "gilrs")]
+    gilrs: Option<engine::input::GilrsBackend>,
 }
 
 impl App {
     fn new() -> Self {
+        let config = load_bindings_config();
         Self {
             window: None, context: None, surface: None,
             game: Game::new(),
             last_time: Instant::now(),
             accumulator: 0.0,
+            input: EngineInput::new(),
+            p1_source: config.player1.source,
+            p2_source: config.player2.source,
+            #[cfg(feature = "gilrs")]
+            gilrs: engine::input::GilrsBackend::new().ok(),
         }
     }
 }
@@ -75,22 +181,17 @@ impl ApplicationHandler for App {
             WindowEvent::CloseRequested => event_loop.exit(),
             WindowEvent::KeyboardInput { event, .. } => {
                 let pressed = event.state.is_pressed();
-                if let PhysicalKey::Code(key) = event.physical_key {
-                    match key {
-                        KeyCode::Escape => event_loop.exit(),
-                        // Player 1: WASD + F/G
-                        KeyCode::KeyA => self.game.p1.input.left = pressed,
-                        KeyCode::KeyD => self.game.p1.input.right = pressed,
-                        KeyCode::KeyW => { if pressed { self.game.p1.input.jump = true; } }
-                        KeyCode::KeyF => { if pressed { self.game.p1.input.punch = true; } }
-                        KeyCode::KeyG => { if pressed { self.game.p1.input.kick = true; } }
-                        // Player 2: Arrows + K/L
-                        KeyCode::ArrowLeft => self.game.p2.input.left = pressed,
-                        KeyCode::ArrowRight => self.game.p2.input.right = pressed,
-                        KeyCode::ArrowUp => { if pressed { self.game.p2.input.jump = true; } }
-                        KeyCode::KeyK => { if pressed { self.game.p2.input.punch = true; } }
-                        KeyCode::KeyL => { if pressed { self.game.p2.input.kick = true; } }
-                        _ => {}
+                if let PhysicalKey::Code(code) = event.physical_key {
+                    if code == KeyCode::Escape {
+                        if pressed {
+                            event_loop.exit();
+                        }
+                    } else if let Some(key) = map_key(code) {
+                        if pressed {
+                            self.input.key_pressed(key);
+                        } else {
+                            self.input.key_released(key);
+                        }
                     }
                 }
             }
@@ -114,10 +215,30 @@ impl ApplicationHandler for App {
         let dt = now.duration_since(self.last_time).as_secs_f64();
         self.last_time = now;
         self.accumulator += dt;
+
+        #[cfg(feature = "gilrs")]
+        if let Some(gilrs) = &mut self.gilrs {
+            gilrs.poll(self.input.gamepads_mut());
+        }
+
+        // Resolve each player's configured source once per real frame (not
+        // per fixed-update substep) - `Game::update_fighter` already treats
+        // jump/punch/kick as one-shot and clears them itself, the same way
+        // the old direct-from-winit field writes did.
+        let p1 = poll_player(&self.p1_source, p1_keyboard(), &self.input);
+        let p2 = poll_player(&self.p2_source, p2_keyboard(), &self.input);
+        self.game.p1.input = Input { left: p1.left, right: p1.right, jump: p1.jump, punch: p1.punch, kick: p1.kick };
+        self.game.p2.input = Input { left: p2.left, right: p2.right, jump: p2.jump, punch: p2.punch, kick: p2.kick };
+
         while self.accumulator >= FIXED_DT {
             self.game.update(FIXED_DT as f32);
             self.accumulator -= FIXED_DT;
         }
+
+        // Clears pressed/released edges so the next batch of winit key
+        // events starts from a clean slate.
+        self.input.begin_frame();
+
         if let Some(window) = &self.window { window.request_redraw(); }
     }
 }
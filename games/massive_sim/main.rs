@@ -11,6 +11,7 @@
 use std::num::NonZeroU32;
 use std::rc::Rc;
 use std::time::Instant;
+use engine::math::XorShift;
 use softbuffer::{Context, Surface};
 use winit::{
     application::ApplicationHandler,
@@ -113,6 +114,10 @@ struct Game {
     velocities_y: Vec<f32>,
     colors: Vec<u32>,
     frame: u64,
+    /// Seeded RNG driving the per-entity steering jitter below; replaces the
+    /// old position-derived LCG so the sequence no longer depends on float
+    /// layout and can be reseeded/reproduced.
+    rng: XorShift,
 }
 
 impl Game {
@@ -122,7 +127,7 @@ impl Game {
         let mut velocities_x = Vec::with_capacity(count);
         let mut velocities_y = Vec::with_capacity(count);
         let mut colors = Vec::with_capacity(count);
-        
+
         for i in 0..count {
             positions_x.push((i as f32 * 7.3) % WIDTH as f32);
             positions_y.push((i as f32 * 11.7) % HEIGHT as f32);
@@ -136,8 +141,16 @@ impl Game {
                 _ => 0x00FF00FF,
             });
         }
-        
-        Self { positions_x, positions_y, velocities_x, velocities_y, colors, frame: 0 }
+
+        Self {
+            positions_x,
+            positions_y,
+            velocities_x,
+            velocities_y,
+            colors,
+            frame: 0,
+            rng: XorShift::new(0xC0FF_EE),
+        }
     }
     
     fn update(&mut self, dt: f32) {
@@ -167,9 +180,9 @@ impl Game {
                 self.velocities_y[i] = -self.velocities_y[i].abs();
             }
             
-            // Simple steering (deterministic)
-            let seed = (self.positions_x[i] * 100.0 + self.positions_y[i]) as i32;
-            let rand = ((seed.wrapping_mul(1103515245).wrapping_add(12345)) % 1000) as f32 / 1000.0 - 0.5;
+            // Simple steering, driven by the seeded RNG instead of a
+            // position-derived LCG.
+            let rand = self.rng.next_f32() - 0.5;
             self.velocities_x[i] += rand * 50.0 * dt;
             self.velocities_y[i] += rand * 50.0 * dt;
             
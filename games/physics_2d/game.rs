@@ -7,14 +7,41 @@ use engine::{
     math::Vec2,
     physics::{PhysicsWorld, Body},
     render::{Renderer, colors},
+    render::particles::{EmitMode, Emitter, ParticleRng, ParticleSystem},
     input::{InputState, Key},
     core::{GameLoop, Timer},
+    core::rollback::{buttons, FrameInputs, PlayerInput},
+    ai::{NeuralController, Population},
+    rollback::Simulation,
 };
 
 const GRAVITY: f32 = 980.0;
 const PLAYER_SPEED: f32 = 200.0;
 const JUMP_FORCE: f32 = -400.0;
 
+/// Acceleration the neural controllers can apply to an enemy each second.
+const ENEMY_ACCEL: f32 = 600.0;
+/// Sensor scale keeping network inputs roughly in `[-1, 1]`.
+const SENSOR_SCALE: f32 = 1.0 / 400.0;
+/// Fixed steps per evolution episode.
+const EPISODE_TICKS: u32 = 180;
+/// Number of enemy balls (and therefore brains in the population).
+const ENEMY_COUNT: usize = 10;
+
+/// Starting hit points for the player and for each enemy ball.
+const PLAYER_HEALTH: f32 = 100.0;
+const ENEMY_HEALTH: f32 = 30.0;
+/// Contact damage dealt by a colliding entity per impact.
+const PLAYER_DAMAGE: f32 = 12.0;
+const ENEMY_DAMAGE: f32 = 6.0;
+/// Minimum normal impulse before a contact counts as a damaging hit, so resting
+/// stacks don't chip away at health.
+const HIT_IMPULSE: f32 = 40.0;
+
+/// Fixed simulation timestep used on the rollback path. Hard-coded rather than
+/// read from the wall-clock loop so re-simulation reproduces identical state.
+const FIXED_DT: f32 = 1.0 / 60.0;
+
 pub struct Physics2DGame {
     config: EngineConfig,
     world: World,
@@ -23,6 +50,21 @@ pub struct Physics2DGame {
     input: InputState,
     game_loop: GameLoop,
     player_id: Option<hecs::Entity>,
+    /// Presentation-only effect pool (hit sparks, jump dust). Not part of the
+    /// rollback snapshot.
+    particles: ParticleSystem,
+    spark: Emitter,
+    particle_rng: ParticleRng,
+    /// Evolving brains steering the enemy balls toward the player.
+    enemy_brains: Population,
+    controllers: Vec<NeuralController>,
+    enemy_ids: Vec<hecs::Entity>,
+    /// Physics body index → owning entity, filled in [`setup`](Self::setup) in
+    /// body-creation order. Lets [`drain_collision_events`] map impacts back to
+    /// ECS entities for damage resolution.
+    body_to_entity: Vec<hecs::Entity>,
+    fitness: Vec<f32>,
+    episode_tick: u32,
     running: bool,
 }
 
@@ -39,12 +81,46 @@ impl Physics2DGame {
             input: InputState::new(),
             game_loop,
             player_id: None,
+            particles: ParticleSystem::new(),
+            // Orange spark that fades to transparent, inheriting a little of the
+            // emitting body's velocity.
+            spark: Emitter {
+                start_color: 0xFFFFAA33,
+                end_color: 0x00FF3300,
+                size: 3,
+                lifetime: 18,
+                lifetime_jitter: 6,
+                speed: 2.5,
+                velocity_inherit: 0.25,
+                mode: EmitMode::Burst { count: 12 },
+            },
+            particle_rng: ParticleRng::new(0x5eed_1234),
+            // 7 sensors → hidden → 2 acceleration outputs, one brain per ball.
+            enemy_brains: Population::new(ENEMY_COUNT, vec![7, 8, 2], 0xA1_C0DE),
+            controllers: Vec::new(),
+            enemy_ids: Vec::new(),
+            body_to_entity: Vec::new(),
+            fitness: Vec::new(),
+            episode_tick: 0,
             running: true,
         };
-        
+
         game.setup();
+        game.bind_controllers();
         game
     }
+
+    /// Re-seat one [`NeuralController`] per enemy from the current population.
+    fn bind_controllers(&mut self) {
+        self.controllers = self
+            .enemy_brains
+            .agents
+            .iter()
+            .cloned()
+            .map(NeuralController::new)
+            .collect();
+        self.fitness = vec![0.0; self.enemy_ids.len()];
+    }
     
     fn setup(&mut self) {
         // Create player
@@ -55,53 +131,62 @@ impl Physics2DGame {
             Collider::Circle { radius: 16.0 },
             Sprite { color: colors::CYAN, width: 32, height: 32 },
             Player,
+            Health::new(PLAYER_HEALTH),
+            Damage { amount: PLAYER_DAMAGE },
         ));
         self.player_id = Some(player);
-        
+
         // Add player to physics
         self.physics.add_body(Body::new(Vec2::new(100.0, 300.0), 1.0));
-        
+        self.body_to_entity.push(player);
+
         // Create ground
-        self.world.spawn((
+        let ground = self.world.spawn((
             Transform::new(400.0, 550.0),
             RigidBody::static_body(),
             Collider::AABB { half_extents: Vec2::new(400.0, 25.0) },
             Sprite { color: colors::GREEN, width: 800, height: 50 },
         ));
         self.physics.add_body(Body::static_body(Vec2::new(400.0, 550.0)));
-        
+        self.body_to_entity.push(ground);
+
         // Create platforms
         for i in 0..5 {
             let x = 150.0 + i as f32 * 150.0;
             let y = 450.0 - i as f32 * 60.0;
-            
-            self.world.spawn((
+
+            let platform = self.world.spawn((
                 Transform::new(x, y),
                 RigidBody::static_body(),
                 Collider::AABB { half_extents: Vec2::new(50.0, 10.0) },
                 Sprite { color: colors::YELLOW, width: 100, height: 20 },
             ));
             self.physics.add_body(Body::static_body(Vec2::new(x, y)));
+            self.body_to_entity.push(platform);
         }
-        
+
         // Create some bouncing balls (enemies/bullets)
-        for i in 0..10 {
+        for i in 0..ENEMY_COUNT {
             let x = 100.0 + i as f32 * 70.0;
             let y = 100.0 + (i % 3) as f32 * 50.0;
-            
-            self.world.spawn((
+
+            let enemy = self.world.spawn((
                 Transform::new(x, y),
                 Velocity { linear: Vec2::new(50.0, 0.0), angular: 0.0 },
                 RigidBody::new(0.5),
                 Collider::Circle { radius: 8.0 },
                 Sprite { color: colors::RED, width: 16, height: 16 },
                 Enemy,
+                Health::new(ENEMY_HEALTH),
+                Damage { amount: ENEMY_DAMAGE },
             ));
-            
+            self.enemy_ids.push(enemy);
+
             let mut body = Body::new(Vec2::new(x, y), 0.5);
             body.velocity = Vec2::new(50.0, 0.0);
             body.restitution = 0.9;
             self.physics.add_body(body);
+            self.body_to_entity.push(enemy);
         }
         
         log::info!("Physics 2D game initialized with {} entities", self.world.entity_count());
@@ -141,39 +226,234 @@ impl Physics2DGame {
     }
     
     fn fixed_update(&mut self, dt: f32) {
-        // Player input
-        self.handle_input(dt);
-        
-        // Physics step (uses ASM for collision/integration)
+        // Sample the local device into the same fixed-size input the rollback
+        // path uses, so both paths share one deterministic step.
+        let input = self.sample_local_input();
+        self.fixed_step(&input, dt);
+    }
+
+    /// Translate the current [`InputState`] into a [`PlayerInput`] for the
+    /// deterministic step.
+    fn sample_local_input(&self) -> PlayerInput {
+        let mut buttons = 0u16;
+        if self.input.is_key_down(Key::A) || self.input.is_key_down(Key::Left) {
+            buttons |= self::buttons::LEFT;
+        }
+        if self.input.is_key_down(Key::D) || self.input.is_key_down(Key::Right) {
+            buttons |= self::buttons::RIGHT;
+        }
+        if self.input.is_key_pressed(Key::Space) {
+            buttons |= self::buttons::JUMP;
+        }
+        PlayerInput { buttons, move_x: 0, move_y: 0 }
+    }
+
+    /// One deterministic step driven entirely by `input` (no device or
+    /// wall-clock reads), shared by the local loop and rollback re-simulation.
+    fn fixed_step(&mut self, input: &PlayerInput, dt: f32) {
+        self.handle_input(input, dt);
+        self.steer_enemies(dt);
+        self.run_episode_clock();
+
+        // Burst hit sparks from the player on jump, inheriting its velocity.
+        if input.held(buttons::JUMP) {
+            if let Some(player_id) = self.player_id {
+                let origin = self
+                    .world
+                    .get::<Transform>(player_id)
+                    .map(|t| t.position)
+                    .unwrap_or(Vec2::ZERO);
+                let vel = self
+                    .world
+                    .get::<Velocity>(player_id)
+                    .map(|v| v.linear)
+                    .unwrap_or(Vec2::ZERO);
+                let spark = self.spark;
+                self.particles.emit(&spark, origin, vel, dt, &mut self.particle_rng);
+            }
+        }
+
         self.physics.step();
-        
-        // Sync physics to ECS
+        self.resolve_collisions(dt);
+        self.particles.fixed_update();
         self.sync_physics_to_ecs();
     }
-    
-    fn handle_input(&mut self, dt: f32) {
+
+    /// Drain this step's collision events, apply contact damage between any
+    /// [`Damage`] dealer and [`Health`] target, spark each hit, and despawn
+    /// entities brought to zero health. Runs deterministically off the physics
+    /// event queue, so re-simulation reproduces the same despawns.
+    fn resolve_collisions(&mut self, dt: f32) {
+        let events = self.physics.drain_collision_events();
+        let mut dead: Vec<hecs::Entity> = Vec::new();
+        for ev in events {
+            if ev.impulse < HIT_IMPULSE {
+                continue;
+            }
+            let (Some(&ea), Some(&eb)) =
+                (self.body_to_entity.get(ev.a), self.body_to_entity.get(ev.b))
+            else {
+                continue;
+            };
+
+            let hit_a = self.apply_contact_damage(eb, ea, &mut dead);
+            let hit_b = self.apply_contact_damage(ea, eb, &mut dead);
+            if hit_a || hit_b {
+                // Spark from the contact point (midpoint of the two bodies).
+                let pa = self.world.get::<Transform>(ea).map(|t| t.position);
+                let pb = self.world.get::<Transform>(eb).map(|t| t.position);
+                if let (Some(pa), Some(pb)) = (pa, pb) {
+                    let spark = self.spark;
+                    let origin = (pa + pb) * 0.5;
+                    self.particles.emit(&spark, origin, Vec2::ZERO, dt, &mut self.particle_rng);
+                }
+            }
+        }
+
+        for entity in dead {
+            self.despawn_entity(entity);
+        }
+    }
+
+    /// Subtract `dealer`'s [`Damage`] from `target`'s [`Health`], queuing the
+    /// target for despawn if the hit killed it. Returns whether any damage was
+    /// dealt.
+    fn apply_contact_damage(
+        &mut self,
+        dealer: hecs::Entity,
+        target: hecs::Entity,
+        dead: &mut Vec<hecs::Entity>,
+    ) -> bool {
+        let Some(amount) = self.world.get::<Damage>(dealer).map(|d| d.amount) else {
+            return false;
+        };
+        let Some(mut health) = self.world.get_mut::<Health>(target) else {
+            return false;
+        };
+        health.damage(amount);
+        if health.is_dead() && !dead.contains(&target) {
+            dead.push(target);
+        }
+        true
+    }
+
+    /// Remove a killed entity from the world and the controller bookkeeping so
+    /// its brain and fitness slot no longer advance. The physics body is left in
+    /// place; its `body_to_entity` slot simply stops resolving to a live entity.
+    fn despawn_entity(&mut self, entity: hecs::Entity) {
+        if let Some(idx) = self.enemy_ids.iter().position(|&e| e == entity) {
+            self.enemy_ids.remove(idx);
+            if idx < self.controllers.len() {
+                self.controllers.remove(idx);
+            }
+            if idx < self.fitness.len() {
+                self.fitness.remove(idx);
+            }
+        }
+        if self.player_id == Some(entity) {
+            self.player_id = None;
+        }
+        let _ = self.world.despawn(entity);
+    }
+
+    fn handle_input(&mut self, input: &PlayerInput, dt: f32) {
         if let Some(player_id) = self.player_id {
             if let Some(mut vel) = self.world.get_mut::<Velocity>(player_id) {
                 // Horizontal movement
-                if self.input.is_key_down(Key::A) || self.input.is_key_down(Key::Left) {
+                if input.held(buttons::LEFT) {
                     vel.linear.x = -PLAYER_SPEED;
-                } else if self.input.is_key_down(Key::D) || self.input.is_key_down(Key::Right) {
+                } else if input.held(buttons::RIGHT) {
                     vel.linear.x = PLAYER_SPEED;
                 } else {
                     vel.linear.x *= 0.9; // Friction
                 }
-                
+
                 // Jump
-                if self.input.is_key_pressed(Key::Space) {
+                if input.held(buttons::JUMP) {
                     vel.linear.y = JUMP_FORCE;
                 }
-                
+
                 // Gravity
                 vel.linear.y += GRAVITY * dt;
             }
         }
     }
     
+    /// Drive every enemy ball through its neural controller: build a sensor
+    /// vector from the ECS, `think`, and apply the output as linear
+    /// acceleration. Proximity to the player accrues fitness for the next
+    /// generation.
+    fn steer_enemies(&mut self, dt: f32) {
+        let Some(player) = self.player_id else { return };
+        let (player_pos, player_vel) = {
+            let pos = self.world.get::<Transform>(player).map(|t| t.position);
+            let vel = self.world.get::<Velocity>(player).map(|v| v.linear);
+            match (pos, vel) {
+                (Some(p), Some(v)) => (p, v),
+                _ => return,
+            }
+        };
+
+        // Static obstacle positions (platforms/ground) for the nearest-wall sensor.
+        let mut obstacles: Vec<Vec2> = Vec::new();
+        for (e, (transform, _)) in self.world.query::<(&Transform, &Collider)>().iter() {
+            if Some(e) != self.player_id && !self.enemy_ids.contains(&e) {
+                obstacles.push(transform.position);
+            }
+        }
+
+        for idx in 0..self.enemy_ids.len() {
+            let eid = self.enemy_ids[idx];
+            let (pos, vel) = {
+                let pos = self.world.get::<Transform>(eid).map(|t| t.position);
+                let vel = self.world.get::<Velocity>(eid).map(|v| v.linear);
+                match (pos, vel) {
+                    (Some(p), Some(v)) => (p, v),
+                    _ => continue,
+                }
+            };
+
+            let rel_pos = player_pos - pos;
+            let rel_vel = player_vel - vel;
+            let nearest = obstacles
+                .iter()
+                .map(|o| o.distance(pos))
+                .fold(f32::MAX, f32::min);
+            let inputs = [
+                rel_pos.x * SENSOR_SCALE,
+                rel_pos.y * SENSOR_SCALE,
+                rel_vel.x * SENSOR_SCALE,
+                rel_vel.y * SENSOR_SCALE,
+                nearest * SENSOR_SCALE,
+                vel.x * SENSOR_SCALE,
+                vel.y * SENSOR_SCALE,
+            ];
+
+            let out = self.controllers[idx].think(&inputs);
+            let ax = out.first().copied().unwrap_or(0.0).clamp(-1.0, 1.0);
+            let ay = out.get(1).copied().unwrap_or(0.0).clamp(-1.0, 1.0);
+            if let Some(mut v) = self.world.get_mut::<Velocity>(eid) {
+                v.linear.x += ax * ENEMY_ACCEL * dt;
+                v.linear.y += ay * ENEMY_ACCEL * dt;
+            }
+
+            // Reward closing on the player (chase); survival accrues implicitly.
+            self.fitness[idx] += 1.0 / (1.0 + rel_pos.length());
+        }
+    }
+
+    /// Advance the episode clock and, at its end, evolve the population and
+    /// re-seat the controllers for the next generation.
+    fn run_episode_clock(&mut self) {
+        self.episode_tick += 1;
+        if self.episode_tick >= EPISODE_TICKS {
+            self.episode_tick = 0;
+            let fitness = std::mem::take(&mut self.fitness);
+            self.enemy_brains.evolve(&fitness);
+            self.bind_controllers();
+        }
+    }
+
     fn sync_physics_to_ecs(&mut self) {
         // In a real implementation, sync physics body positions to ECS transforms
         // This is where ASM-accelerated physics results flow back to game logic
@@ -181,12 +461,135 @@ impl Physics2DGame {
     
     fn render(&mut self, _interpolation: f32) {
         self.renderer.clear(colors::BLACK);
-        
+
         // Render all sprites
         for (_, (transform, sprite)) in self.world.query::<(&Transform, &Sprite)>().iter() {
             let x = transform.position.x as i32 - sprite.width as i32 / 2;
             let y = transform.position.y as i32 - sprite.height as i32 / 2;
             self.renderer.fill_rect(x, y, sprite.width, sprite.height, sprite.color);
         }
+
+        // Effects draw on top of the sprites, fading by age.
+        self.particles.render(&mut self.renderer);
+    }
+}
+
+/// Rollback hook: the match is a deterministic simulation of the physics world
+/// plus the ECS transforms/velocities. Combat despawns are driven off the
+/// deterministic physics event queue, so re-simulation reproduces the same
+/// despawns given the same inputs - but a *rollback* to a frame snapshotted
+/// before a kill still needs to put the killed entity's motion back, so each
+/// record is keyed by `hecs::Entity::to_bits()` rather than assumed to line up
+/// positionally with the live world's current (possibly smaller, reordered by
+/// despawn) query order. `restore` fails closed if the keyed set in `bytes`
+/// doesn't exactly match the live world's, rather than zipping mismatched data
+/// together silently.
+impl Simulation for Physics2DGame {
+    type Input = FrameInputs;
+
+    fn fixed_update(&mut self, input: &Self::Input) {
+        // Player 0 drives the local avatar; remaining slots are reserved for
+        // additional networked players.
+        self.fixed_step(&input[0], FIXED_DT);
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        let mut bytes = self.physics.snapshot();
+        // Append the ECS motion state, one entity-keyed record per live
+        // entity: a stable `to_bits()` key plus its four f32 fields, so
+        // `restore` can match records back up after entities are despawned
+        // or reordered instead of trusting query order to stay aligned.
+        let entities: Vec<_> = self.world.query::<(&Transform, &Velocity)>().iter().collect();
+        bytes.extend_from_slice(&(entities.len() as u32).to_le_bytes());
+        for (entity, (transform, velocity)) in entities {
+            bytes.extend_from_slice(&entity.to_bits().get().to_le_bytes());
+            for f in [
+                transform.position.x,
+                transform.position.y,
+                velocity.linear.x,
+                velocity.linear.y,
+            ] {
+                bytes.extend_from_slice(&f.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Returns `false` (leaving the game untouched) if `bytes` is truncated,
+    /// doesn't parse, or its keyed entity set doesn't exactly match the live
+    /// world's - rather than silently zipping mismatched motion records onto
+    /// the wrong entities after a despawn has changed the live set.
+    fn restore(&mut self, bytes: &[u8]) -> bool {
+        // The physics snapshot is self-describing (leading body count); the ECS
+        // payload follows it in the same order `snapshot` wrote.
+        // Each body serializes as ten f32 fields + a shape tag byte + three f32
+        // shape payloads + two u32 layer/mask fields = 61 bytes; see
+        // `PhysicsWorld::snapshot`.
+        let body_bytes = 4 + self.physics.body_count() * 61;
+        let Some(body_slice) = bytes.get(..body_bytes) else {
+            return false;
+        };
+
+        fn read_u32(bytes: &[u8], cursor: &mut usize) -> Option<u32> {
+            let v = u32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+            *cursor += 4;
+            Some(v)
+        }
+        fn read_u64(bytes: &[u8], cursor: &mut usize) -> Option<u64> {
+            let v = u64::from_le_bytes(bytes.get(*cursor..*cursor + 8)?.try_into().ok()?);
+            *cursor += 8;
+            Some(v)
+        }
+        fn read_f32(bytes: &[u8], cursor: &mut usize) -> Option<f32> {
+            let v = f32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+            *cursor += 4;
+            Some(v)
+        }
+
+        let mut cursor = body_bytes;
+        let Some(record_count) = read_u32(bytes, &mut cursor) else {
+            return false;
+        };
+        let mut motion = std::collections::HashMap::with_capacity(record_count as usize);
+        for _ in 0..record_count {
+            let Some(key) = read_u64(bytes, &mut cursor) else {
+                return false;
+            };
+            let (Some(x), Some(y), Some(vx), Some(vy)) = (
+                read_f32(bytes, &mut cursor),
+                read_f32(bytes, &mut cursor),
+                read_f32(bytes, &mut cursor),
+                read_f32(bytes, &mut cursor),
+            ) else {
+                return false;
+            };
+            motion.insert(key, (x, y, vx, vy));
+        }
+
+        // The snapshot's keyed entity set must exactly match the live world's
+        // before anything is mutated - otherwise a kill that happened between
+        // `bytes` being captured and now would leave some entities with no
+        // recorded motion (or vice versa).
+        let live: Vec<hecs::Entity> =
+            self.world.query::<(&Transform, &Velocity)>().iter().map(|(e, _)| e).collect();
+        if live.len() != motion.len()
+            || !live.iter().all(|e| motion.contains_key(&e.to_bits().get()))
+        {
+            return false;
+        }
+
+        if !self.physics.restore(body_slice) {
+            return false;
+        }
+        for entity in live {
+            let (x, y, vx, vy) = motion[&entity.to_bits().get()];
+            let mut transform = self.world.get_mut::<Transform>(entity).expect("checked above");
+            let mut velocity = self.world.get_mut::<Velocity>(entity).expect("checked above");
+            transform.position.x = x;
+            transform.position.y = y;
+            velocity.linear.x = vx;
+            velocity.linear.y = vy;
+        }
+        true
     }
 }
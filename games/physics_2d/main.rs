@@ -12,6 +12,10 @@
 use std::num::NonZeroU32;
 use std::rc::Rc;
 use std::time::Instant;
+use engine::ai::{Activation, Network, Population};
+use engine::math::Vec2;
+use engine::physics::collision::{swept_aabb, Sweep};
+use engine::rollback::{Session, Simulation};
 use softbuffer::{Context, Surface};
 use winit::{
     application::ApplicationHandler,
@@ -48,7 +52,13 @@ struct App {
     window: Option<Rc<Window>>,
     context: Option<Context<Rc<Window>>>,
     surface: Option<Surface<Rc<Window>, Rc<Window>>>,
-    game: Game,
+    /// Rollback session wrapping the deterministic `Game` step loop. In this
+    /// single-player demo no remote peer corrects inputs, but the session keeps
+    /// the ring buffer of confirmed snapshots ready for P2P netplay.
+    session: Session<Game>,
+    /// Currently-held input, owned by the harness and fed to the session each
+    /// fixed step so `Game::fixed_update` stays pure in `(state, input)`.
+    input: Input,
     last_time: Instant,
     accumulator: f64,
 }
@@ -59,7 +69,8 @@ impl App {
             window: None,
             context: None,
             surface: None,
-            game: Game::new(),
+            session: Session::new(Game::new(), 8),
+            input: Input::default(),
             last_time: Instant::now(),
             accumulator: 0.0,
         }
@@ -94,13 +105,13 @@ impl ApplicationHandler for App {
                 if let PhysicalKey::Code(key) = event.physical_key {
                     match key {
                         KeyCode::Escape => event_loop.exit(),
-                        KeyCode::KeyA | KeyCode::ArrowLeft => self.game.input.left = pressed,
-                        KeyCode::KeyD | KeyCode::ArrowRight => self.game.input.right = pressed,
+                        KeyCode::KeyA | KeyCode::ArrowLeft => self.input.left = pressed,
+                        KeyCode::KeyD | KeyCode::ArrowRight => self.input.right = pressed,
                         KeyCode::KeyW | KeyCode::ArrowUp | KeyCode::Space => {
-                            if pressed && !self.game.input.jump {
-                                self.game.input.jump_pressed = true;
+                            if pressed && !self.input.jump {
+                                self.input.jump_pressed = true;
                             }
-                            self.game.input.jump = pressed;
+                            self.input.jump = pressed;
                         }
                         _ => {}
                     }
@@ -113,7 +124,7 @@ impl ApplicationHandler for App {
                         surface.resize(w, h).expect("Failed to resize surface");
                         
                         let mut buffer = surface.buffer_mut().expect("Failed to get buffer");
-                        self.game.render(&mut buffer, size.width, size.height);
+                        self.session.simulation().render(&mut buffer, size.width, size.height);
                         buffer.present().expect("Failed to present buffer");
                     }
                 }
@@ -128,9 +139,15 @@ impl ApplicationHandler for App {
         self.last_time = now;
         
         self.accumulator += dt;
-        
+
         while self.accumulator >= FIXED_DT {
-            self.game.fixed_update(FIXED_DT as f32);
+            // Store the local input and advance the session one confirmed frame.
+            // A real netplay client would predict the remote input here and call
+            // `session.confirm_input` when the authoritative value arrives.
+            let input = self.input.clone();
+            self.session.advance(input);
+            // The jump edge is consumed by the step we just ran.
+            self.input.jump_pressed = false;
             self.accumulator -= FIXED_DT;
         }
         
@@ -140,7 +157,7 @@ impl ApplicationHandler for App {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default, PartialEq)]
 struct Input {
     left: bool,
     right: bool,
@@ -148,6 +165,16 @@ struct Input {
     jump_pressed: bool,
 }
 
+/// A one-half-box ramp. `facing` selects the high corner (`+1` = high on the
+/// right, `-1` = high on the left); the exposed surface drops by `rise/run`
+/// units of `y` per unit `x` away from that corner.
+#[derive(Clone, Copy)]
+struct Slope {
+    rise: f32,
+    run: f32,
+    facing: i8,
+}
+
 struct Entity {
     x: f32,
     y: f32,
@@ -157,19 +184,118 @@ struct Entity {
     h: f32,
     color: u32,
     grounded: bool,
+    /// Only blocks a mover landing on top (jump-through ledge).
+    one_way: bool,
+    /// When set, the top face is the ramp line rather than a flat edge.
+    slope: Option<Slope>,
 }
 
 impl Entity {
     fn new(x: f32, y: f32, w: f32, h: f32, color: u32) -> Self {
-        Self { x, y, vx: 0.0, vy: 0.0, w, h, color, grounded: false }
+        Self {
+            x,
+            y,
+            vx: 0.0,
+            vy: 0.0,
+            w,
+            h,
+            color,
+            grounded: false,
+            one_way: false,
+            slope: None,
+        }
+    }
+
+    /// Mark this platform as a jump-through / one-way surface.
+    fn one_way(mut self) -> Self {
+        self.one_way = true;
+        self
     }
+
+    /// Turn this platform into a ramp with the given gradient and facing.
+    fn with_slope(mut self, rise: f32, run: f32, facing: i8) -> Self {
+        self.slope = Some(Slope { rise, run, facing });
+        self
+    }
+
+    /// Height of the ramp surface at world-x `px`, or `None` when this is not a
+    /// slope or `px` falls outside its horizontal span. Follows
+    /// `y = top + from_high * (rise/run)` so the high corner selected by
+    /// `facing` sits at the box top.
+    fn slope_surface_y(&self, px: f32) -> Option<f32> {
+        let s = self.slope?;
+        let left = self.x - self.w / 2.0;
+        let right = self.x + self.w / 2.0;
+        if px < left || px > right {
+            return None;
+        }
+        let top = self.y - self.h / 2.0;
+        let from_high = if s.facing >= 0 { right - px } else { px - left };
+        Some(top + from_high * (s.rise / s.run))
+    }
+}
+
+/// Earliest swept-AABB impact of a moving box (centre `origin`, half-extents
+/// `half`, displacement `d`) against any platform, or `None` if it clears them
+/// all this step.
+fn earliest_sweep(platforms: &[Entity], origin: Vec2, half: Vec2, d: Vec2) -> Option<Sweep> {
+    let mut nearest: Option<Sweep> = None;
+    for p in platforms {
+        // Slopes are resolved by the dedicated feet-snapping pass, not as boxes.
+        if p.slope.is_some() {
+            continue;
+        }
+        if let Some(hit) = swept_aabb(
+            origin,
+            half,
+            d,
+            Vec2::new(p.x, p.y),
+            Vec2::new(p.w / 2.0, p.h / 2.0),
+        ) {
+            // A one-way platform only blocks a mover descending onto its top.
+            if p.one_way && (d.y <= 0.0 || hit.normal.y >= 0.0) {
+                continue;
+            }
+            if nearest.map_or(true, |n| hit.toi < n.toi) {
+                nearest = Some(hit);
+            }
+        }
+    }
+    nearest
+}
+
+/// Snap a body's feet onto any ramp it is standing within tolerance of,
+/// grounding it and projecting its velocity along the slope tangent. Returns
+/// `true` when a ramp caught the body. `half_h` is the body's half-height.
+fn resolve_slope(platforms: &[Entity], x: f32, y: &mut f32, v: &mut Vec2, half_h: f32) -> bool {
+    const TOLERANCE: f32 = 8.0;
+    for p in platforms {
+        let Some(surface) = p.slope_surface_y(x) else { continue };
+        let s = p.slope.unwrap();
+        let feet = *y + half_h;
+        // Catch the body when its feet are at or just below the ramp line.
+        if feet >= surface - TOLERANCE && feet <= surface + half_h {
+            *y = surface - half_h;
+            // Tangent points to the right along the ramp; +1 facing is high on
+            // the right, so the surface gradient flips with `facing`.
+            let gradient = if s.facing >= 0 { -(s.rise / s.run) } else { s.rise / s.run };
+            let tangent = Vec2::new(1.0, gradient).normalize();
+            *v = tangent * v.dot(tangent);
+            return true;
+        }
+    }
+    false
 }
 
 struct Game {
     player: Entity,
     platforms: Vec<Entity>,
     balls: Vec<Entity>,
-    input: Input,
+    /// One evolved brain per ball, steering it toward the player. Weights are
+    /// fixed for the lifetime of a run (a headless trainer breeds the next
+    /// generation via `Population::evolve`), so `step` stays deterministic and
+    /// rollback-safe without serializing the networks.
+    brains: Vec<Network>,
     frame: u64,
 }
 
@@ -182,14 +308,24 @@ impl Game {
         ];
         
         for i in 0..5 {
-            platforms.push(Entity::new(
-                150.0 + i as f32 * 140.0,
-                480.0 - i as f32 * 50.0,
-                100.0,
-                20.0,
-                YELLOW,
-            ));
+            // The upper ledges are jump-through so the player can climb them
+            // from below and land on top.
+            platforms.push(
+                Entity::new(
+                    150.0 + i as f32 * 140.0,
+                    480.0 - i as f32 * 50.0,
+                    100.0,
+                    20.0,
+                    YELLOW,
+                )
+                .one_way(),
+            );
         }
+
+        // A ramp leading up to the stack of ledges.
+        platforms.push(
+            Entity::new(300.0, 520.0, 160.0, 40.0, GREEN).with_slope(40.0, 160.0, 1),
+        );
         
         let mut balls = Vec::new();
         for i in 0..8 {
@@ -205,79 +341,149 @@ impl Game {
             balls.push(ball);
         }
         
+        // Seed one brain per ball from generation 0 of a population. Sensors
+        // are [rel player x, rel player y, own vx, own vy]; outputs are two
+        // steering intents. Tanh keeps the steering smooth and bounded.
+        let brains: Vec<Network> = Population::new(balls.len(), vec![4, 6, 2], 0xA1_CE)
+            .agents
+            .into_iter()
+            .map(|n| n.with_activation(Activation::Tanh))
+            .collect();
+
         Self {
             player,
             platforms,
             balls,
-            input: Input::default(),
+            brains,
             frame: 0,
         }
     }
-    
-    fn fixed_update(&mut self, dt: f32) {
+
+    /// Advance one fixed step from explicit input. Pure in `(state, input)`:
+    /// the timestep is the fixed `DT` constant (no wall-clock read) and there
+    /// is no unseeded randomness, so rollback re-simulation reproduces state
+    /// bit-for-bit.
+    fn step(&mut self, input: &Input) {
+        const DT: f32 = FIXED_DT as f32;
         const GRAVITY: f32 = 1200.0;
         const PLAYER_SPEED: f32 = 300.0;
         const JUMP_FORCE: f32 = 500.0;
         const FRICTION: f32 = 0.85;
-        
-        if self.input.left {
+
+        let dt = DT;
+
+        if input.left {
             self.player.vx = -PLAYER_SPEED;
-        } else if self.input.right {
+        } else if input.right {
             self.player.vx = PLAYER_SPEED;
         } else {
             self.player.vx *= FRICTION;
         }
-        
-        if self.input.jump_pressed && self.player.grounded {
+
+        if input.jump_pressed && self.player.grounded {
             self.player.vy = -JUMP_FORCE;
             self.player.grounded = false;
         }
-        self.input.jump_pressed = false;
-        
+
         if !self.player.grounded {
             self.player.vy += GRAVITY * dt;
         }
         
-        self.player.x += self.player.vx * dt;
-        self.player.y += self.player.vy * dt;
-        
+        // Resolve the player against the platforms with continuous sweeps so a
+        // fast fall can't tunnel through a thin ledge in a single step. Advance
+        // to the time of impact, kill the normal-axis velocity and slide along
+        // the tangent with the remaining time; a few iterations handle corners.
+        let half = Vec2::new(self.player.w / 2.0, self.player.h / 2.0);
+        let mut dx = self.player.vx * dt;
+        let mut dy = self.player.vy * dt;
         self.player.grounded = false;
-        for i in 0..self.platforms.len() {
-            let px = self.platforms[i].x;
-            let py = self.platforms[i].y;
-            let pw = self.platforms[i].w;
-            let ph = self.platforms[i].h;
-            
-            if (self.player.x - px).abs() < (self.player.w + pw) / 2.0 &&
-               (self.player.y - py).abs() < (self.player.h + ph) / 2.0 {
-                let overlap_x = (self.player.w / 2.0 + pw / 2.0) - (self.player.x - px).abs();
-                let overlap_y = (self.player.h / 2.0 + ph / 2.0) - (self.player.y - py).abs();
-                
-                if overlap_x < overlap_y {
-                    if self.player.x < px {
-                        self.player.x -= overlap_x;
-                    } else {
-                        self.player.x += overlap_x;
-                    }
-                    self.player.vx = 0.0;
-                } else {
-                    if self.player.y < py {
-                        self.player.y -= overlap_y;
-                        self.player.grounded = true;
+        for _ in 0..4 {
+            let origin = Vec2::new(self.player.x, self.player.y);
+            match earliest_sweep(&self.platforms, origin, half, Vec2::new(dx, dy)) {
+                Some(hit) => {
+                    self.player.x += dx * hit.toi;
+                    self.player.y += dy * hit.toi;
+                    let remaining = 1.0 - hit.toi;
+                    if hit.normal.y != 0.0 {
+                        // Landing on a top face (normal points up) re-grounds us.
+                        if hit.normal.y < 0.0 {
+                            self.player.grounded = true;
+                        }
                         self.player.vy = 0.0;
+                        dy = 0.0;
+                        dx *= remaining;
                     } else {
-                        self.player.y += overlap_y;
-                        self.player.vy = 0.0;
+                        self.player.vx = 0.0;
+                        dx = 0.0;
+                        dy *= remaining;
                     }
                 }
+                None => {
+                    self.player.x += dx;
+                    self.player.y += dy;
+                    break;
+                }
             }
         }
-        
-        for ball in &mut self.balls {
+
+        // Ramps: snap the feet onto any slope the player is walking across.
+        let mut player_vel = Vec2::new(self.player.vx, self.player.vy);
+        if resolve_slope(&self.platforms, self.player.x, &mut self.player.y, &mut player_vel, self.player.h / 2.0) {
+            self.player.grounded = true;
+        }
+        self.player.vx = player_vel.x;
+        self.player.vy = player_vel.y;
+
+        const STEER_ACCEL: f32 = 400.0;
+        let (player_x, player_y) = (self.player.x, self.player.y);
+        for (i, ball) in self.balls.iter_mut().enumerate() {
+            // Let the ball's brain steer it toward the player.
+            let sensors = [
+                (player_x - ball.x) / WIDTH as f32,
+                (player_y - ball.y) / HEIGHT as f32,
+                ball.vx / 200.0,
+                ball.vy / 200.0,
+            ];
+            let controls = self.brains[i].steer(&sensors);
+            ball.vx += controls.x * STEER_ACCEL * dt;
+            ball.vy += controls.y * STEER_ACCEL * dt;
+
             ball.vy += GRAVITY * dt * 0.5;
-            ball.x += ball.vx * dt;
-            ball.y += ball.vy * dt;
-            
+            let half = Vec2::new(ball.w / 2.0, ball.h / 2.0);
+            let mut dx = ball.vx * dt;
+            let mut dy = ball.vy * dt;
+            for _ in 0..4 {
+                let origin = Vec2::new(ball.x, ball.y);
+                match earliest_sweep(&self.platforms, origin, half, Vec2::new(dx, dy)) {
+                    Some(hit) => {
+                        ball.x += dx * hit.toi;
+                        ball.y += dy * hit.toi;
+                        let remaining = 1.0 - hit.toi;
+                        if hit.normal.y != 0.0 {
+                            ball.vy = -ball.vy * 0.8;
+                            dy = 0.0;
+                            dx *= remaining;
+                        } else {
+                            ball.vx = -ball.vx;
+                            dx = 0.0;
+                            dy *= remaining;
+                        }
+                    }
+                    None => {
+                        ball.x += dx;
+                        ball.y += dy;
+                        break;
+                    }
+                }
+            }
+
+            // Let balls roll on ramps too, bouncing a little off the surface.
+            let mut ball_vel = Vec2::new(ball.vx, ball.vy);
+            if resolve_slope(&self.platforms, ball.x, &mut ball.y, &mut ball_vel, ball.h / 2.0) {
+                ball.vy = -ball_vel.y.abs() * 0.4;
+                ball.vx = ball_vel.x;
+            }
+
             if ball.x < ball.w / 2.0 {
                 ball.x = ball.w / 2.0;
                 ball.vx = ball.vx.abs();
@@ -287,27 +493,7 @@ impl Game {
                 ball.vx = -ball.vx.abs();
             }
         }
-        
-        for i in 0..self.balls.len() {
-            for j in 0..self.platforms.len() {
-                let bx = self.balls[i].x;
-                let by = self.balls[i].y;
-                let bw = self.balls[i].w;
-                let bh = self.balls[i].h;
-                let px = self.platforms[j].x;
-                let py = self.platforms[j].y;
-                let pw = self.platforms[j].w;
-                let ph = self.platforms[j].h;
-                
-                if (bx - px).abs() < (bw + pw) / 2.0 && (by - py).abs() < (bh + ph) / 2.0 {
-                    if by < py {
-                        self.balls[i].y = py - ph / 2.0 - bh / 2.0;
-                        self.balls[i].vy = -self.balls[i].vy.abs() * 0.8;
-                    }
-                }
-            }
-        }
-        
+
         self.player.x = self.player.x.clamp(self.player.w / 2.0, WIDTH as f32 - self.player.w / 2.0);
         self.frame += 1;
     }
@@ -401,3 +587,121 @@ impl Game {
         }
     }
 }
+
+impl Entity {
+    /// Append all fields to `out` in a fixed little-endian layout.
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        for f in [self.x, self.y, self.vx, self.vy, self.w, self.h] {
+            out.extend_from_slice(&f.to_le_bytes());
+        }
+        out.extend_from_slice(&self.color.to_le_bytes());
+        out.push(self.grounded as u8);
+        out.push(self.one_way as u8);
+        match self.slope {
+            Some(s) => {
+                out.push(1);
+                out.extend_from_slice(&s.rise.to_le_bytes());
+                out.extend_from_slice(&s.run.to_le_bytes());
+                out.push(s.facing as u8);
+            }
+            None => out.push(0),
+        }
+    }
+
+    /// Read an entity back, advancing `cursor`. Returns `None` on a truncated
+    /// buffer instead of panicking.
+    fn read_bytes(bytes: &[u8], cursor: &mut usize) -> Option<Self> {
+        fn read_f32(bytes: &[u8], cursor: &mut usize) -> Option<f32> {
+            let v = f32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+            *cursor += 4;
+            Some(v)
+        }
+        fn read_u8(bytes: &[u8], cursor: &mut usize) -> Option<u8> {
+            let v = *bytes.get(*cursor)?;
+            *cursor += 1;
+            Some(v)
+        }
+
+        let x = read_f32(bytes, cursor)?;
+        let y = read_f32(bytes, cursor)?;
+        let vx = read_f32(bytes, cursor)?;
+        let vy = read_f32(bytes, cursor)?;
+        let w = read_f32(bytes, cursor)?;
+        let h = read_f32(bytes, cursor)?;
+        let color = u32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+        *cursor += 4;
+        let grounded = read_u8(bytes, cursor)? != 0;
+        let one_way = read_u8(bytes, cursor)? != 0;
+        let slope = if read_u8(bytes, cursor)? != 0 {
+            let rise = read_f32(bytes, cursor)?;
+            let run = read_f32(bytes, cursor)?;
+            let facing = read_u8(bytes, cursor)? as i8;
+            Some(Slope { rise, run, facing })
+        } else {
+            None
+        };
+        Some(Self { x, y, vx, vy, w, h, color, grounded, one_way, slope })
+    }
+}
+
+impl Simulation for Game {
+    type Input = Input;
+
+    fn fixed_update(&mut self, input: &Input) {
+        self.step(input);
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.player.write_bytes(&mut bytes);
+        bytes.extend_from_slice(&(self.platforms.len() as u32).to_le_bytes());
+        for p in &self.platforms {
+            p.write_bytes(&mut bytes);
+        }
+        bytes.extend_from_slice(&(self.balls.len() as u32).to_le_bytes());
+        for b in &self.balls {
+            b.write_bytes(&mut bytes);
+        }
+        bytes.extend_from_slice(&self.frame.to_le_bytes());
+        bytes
+    }
+
+    /// Returns `false` (leaving the game untouched) if `bytes` is truncated
+    /// or otherwise doesn't parse, instead of panicking.
+    fn restore(&mut self, bytes: &[u8]) -> bool {
+        fn read_u32(bytes: &[u8], cursor: &mut usize) -> Option<u32> {
+            let v = u32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+            *cursor += 4;
+            Some(v)
+        }
+        fn read_entities(bytes: &[u8], cursor: &mut usize) -> Option<Vec<Entity>> {
+            let count = read_u32(bytes, cursor)? as usize;
+            let mut entities = Vec::with_capacity(count);
+            for _ in 0..count {
+                entities.push(Entity::read_bytes(bytes, cursor)?);
+            }
+            Some(entities)
+        }
+
+        let mut cursor = 0;
+        let Some(player) = Entity::read_bytes(bytes, &mut cursor) else {
+            return false;
+        };
+        let Some(platforms) = read_entities(bytes, &mut cursor) else {
+            return false;
+        };
+        let Some(balls) = read_entities(bytes, &mut cursor) else {
+            return false;
+        };
+        let Some(frame_bytes) = bytes.get(cursor..cursor + 8) else {
+            return false;
+        };
+        let frame = u64::from_le_bytes(frame_bytes.try_into().unwrap());
+
+        self.player = player;
+        self.platforms = platforms;
+        self.balls = balls;
+        self.frame = frame;
+        true
+    }
+}
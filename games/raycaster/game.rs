@@ -4,8 +4,9 @@ use engine::{
     EngineConfig,
     math::Vec2,
     render::{Renderer, colors, raycast::{Raycaster, RaycastConfig}},
-    input::{InputState, Key},
+    input::{Axis, InputState, Key},
     core::{GameLoop, Timer},
+    content::{ContentError, RaycasterLevel},
 };
 
 const MOVE_SPEED: f32 = 3.0;
@@ -33,7 +34,13 @@ impl RaycasterGame {
             max_distance: 16.0,
             wall_height: 1.0,
         };
-        let raycaster = Raycaster::new(raycast_config, config.width);
+        let mut raycaster = Raycaster::new(raycast_config, config.width);
+        // Use the fixed-point DDA so a given camera pose renders identically on
+        // every machine — the basis for lockstep-safe raycast frames.
+        raycaster.deterministic = true;
+        // Default starting pose (a file-loaded level overrides this).
+        raycaster.position = Vec2::new(8.0, 8.0);
+        raycaster.direction = Vec2::new(-1.0, 0.0);
         
         // Create map (1 = wall, 0 = empty)
         #[rustfmt::skip]
@@ -68,15 +75,40 @@ impl RaycasterGame {
             running: true,
         }
     }
-    
+
+    /// Build a game from a parsed [`RaycasterLevel`], replacing the baked map
+    /// and starting pose. Returns an error if the level fails validation.
+    pub fn from_level(config: EngineConfig, level: &RaycasterLevel) -> Result<Self, ContentError> {
+        level.validate()?;
+        let mut game = Self::new(config);
+        game.map = level.tiles.clone();
+        game.map_width = level.width;
+        game.map_height = level.height;
+        game.raycaster.position = Vec2::new(level.start[0], level.start[1]);
+        game.raycaster.direction = Vec2::new(level.direction[0], level.direction[1]);
+        // Flat cell colors are registered as 1x1 textures so the existing
+        // textured stripe path renders them.
+        for wc in &level.wall_colors {
+            game.raycaster.load_texture(wc.cell, 1, 1, vec![wc.color]);
+        }
+        Ok(game)
+    }
+
+    /// Load a game from a TOML level file's `[raycaster]` section.
+    #[cfg(feature = "serde")]
+    pub fn from_file<P: AsRef<std::path::Path>>(
+        config: EngineConfig,
+        path: P,
+    ) -> Result<Self, ContentError> {
+        let def = engine::content::LevelDef::from_file(path)?;
+        let level = def.raycaster.ok_or(ContentError::MissingSection("raycaster"))?;
+        Self::from_level(config, &level)
+    }
+
     pub fn run(&mut self) {
         log::info!("Starting Raycaster game...");
         log::info!("Controls: WASD to move, Left/Right arrows to rotate, ESC to quit");
-        
-        // Set initial position
-        self.raycaster.position = Vec2::new(8.0, 8.0);
-        self.raycaster.direction = Vec2::new(-1.0, 0.0);
-        
+
         // Simulate frames
         for frame in 0..300 {
             self.input.begin_frame();
@@ -131,6 +163,19 @@ impl RaycasterGame {
         if self.input.is_key_down(Key::D) {
             self.raycaster.strafe(MOVE_SPEED * dt, &self.map, self.map_width);
         }
+
+        // Gamepad: left stick Y drives forward/back, right stick X turns.
+        if let Some(&id) = self.input.gamepads().enumerate().first() {
+            let forward = self.input.gamepad_axis(id, Axis::LeftY);
+            if forward != 0.0 {
+                // Stick forward is negative; forward movement is positive.
+                self.raycaster.move_forward(-forward * MOVE_SPEED * dt, &self.map, self.map_width);
+            }
+            let turn = self.input.gamepad_axis(id, Axis::RightX);
+            if turn != 0.0 {
+                self.raycaster.rotate(turn * ROT_SPEED * dt);
+            }
+        }
     }
     
     fn render(&mut self) {
@@ -12,13 +12,15 @@ use std::num::NonZeroU32;
 use std::rc::Rc;
 use std::time::Instant;
 use softbuffer::{Context, Surface};
+#[cfg(feature = "imgui")]
+use engine::render::overlay::{DevOverlay, Stats, Tunables};
 use winit::{
     application::ApplicationHandler,
     dpi::LogicalSize,
-    event::WindowEvent,
+    event::{DeviceEvent, DeviceId, WindowEvent},
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
     keyboard::{KeyCode, PhysicalKey},
-    window::{Window, WindowAttributes, WindowId},
+    window::{CursorGrabMode, Window, WindowAttributes, WindowId},
 };
 
 const WIDTH: u32 = 640;
@@ -26,6 +28,7 @@ const HEIGHT: u32 = 480;
 const MAP_WIDTH: usize = 16;
 const MAP_HEIGHT: usize = 16;
 const FIXED_DT: f64 = 1.0 / 60.0;
+const TEX_SIZE: usize = 64;
 
 const MAP: [u8; MAP_WIDTH * MAP_HEIGHT] = [
     1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,
@@ -61,6 +64,10 @@ struct App {
     game: Game,
     last_time: Instant,
     accumulator: f64,
+    grabbed: bool,
+    frame_ms: f32,
+    #[cfg(feature = "imgui")]
+    overlay: Option<DevOverlay>,
 }
 
 impl App {
@@ -72,8 +79,28 @@ impl App {
             game: Game::new(),
             last_time: Instant::now(),
             accumulator: 0.0,
+            grabbed: false,
+            frame_ms: 0.0,
+            #[cfg(feature = "imgui")]
+            overlay: None,
         }
     }
+
+    /// Grab + hide (or release + show) the cursor for FPS-style mouse-look.
+    fn set_grab(&mut self, grab: bool) {
+        let Some(window) = &self.window else { return };
+        if grab {
+            // Prefer a locked cursor; fall back to confined where unsupported.
+            let _ = window
+                .set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined));
+            window.set_cursor_visible(false);
+        } else {
+            let _ = window.set_cursor_grab(CursorGrabMode::None);
+            window.set_cursor_visible(true);
+        }
+        self.grabbed = grab;
+    }
 }
 
 impl ApplicationHandler for App {
@@ -86,20 +113,38 @@ impl ApplicationHandler for App {
             let window = Rc::new(event_loop.create_window(attrs).expect("Failed to create window"));
             let context = Context::new(window.clone()).expect("Failed to create context");
             let surface = Surface::new(&context, window.clone()).expect("Failed to create surface");
+            #[cfg(feature = "imgui")]
+            {
+                self.overlay = Some(DevOverlay::new(&window));
+            }
             self.window = Some(window);
             self.context = Some(context);
             self.surface = Some(surface);
+            self.set_grab(true);
         }
     }
     
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+        #[cfg(feature = "imgui")]
+        if let (Some(window), Some(overlay)) = (&self.window, &mut self.overlay) {
+            overlay.handle_event(window, &event);
+        }
         match event {
             WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::Focused(focused) => self.set_grab(focused),
             WindowEvent::KeyboardInput { event, .. } => {
                 let pressed = event.state.is_pressed();
                 if let PhysicalKey::Code(key) = event.physical_key {
                     match key {
-                        KeyCode::Escape => event_loop.exit(),
+                        // Escape releases the pointer rather than quitting, so
+                        // mouse-look can be paused; close via the window button.
+                        KeyCode::Escape if pressed => self.set_grab(false),
+                        #[cfg(feature = "imgui")]
+                        KeyCode::F1 if pressed => {
+                            if let Some(overlay) = &mut self.overlay {
+                                overlay.toggle();
+                            }
+                        }
                         KeyCode::KeyW | KeyCode::ArrowUp => self.game.input.forward = pressed,
                         KeyCode::KeyS | KeyCode::ArrowDown => self.game.input.backward = pressed,
                         KeyCode::KeyA | KeyCode::ArrowLeft => self.game.input.left = pressed,
@@ -115,6 +160,29 @@ impl ApplicationHandler for App {
                         surface.resize(w, h).expect("Failed to resize");
                         let mut buffer = surface.buffer_mut().expect("Failed to get buffer");
                         self.game.render(&mut buffer, size.width, size.height);
+                        #[cfg(feature = "imgui")]
+                        if let Some(overlay) = &mut self.overlay {
+                            let fps = if self.frame_ms > 0.0 { 1000.0 / self.frame_ms } else { 0.0 };
+                            let stats = Stats {
+                                fps,
+                                frame_ms: self.frame_ms,
+                                pos: (self.game.pos_x, self.game.pos_y),
+                                dir: (self.game.dir_x, self.game.dir_y),
+                            };
+                            let mut tunables = Tunables {
+                                move_speed: self.game.move_speed,
+                                rot_speed: self.game.rot_speed,
+                                fov: self.game.fov,
+                                ceiling: self.game.ceiling,
+                                floor: self.game.floor,
+                            };
+                            overlay.render(window, &mut buffer, size.width, size.height, &stats, &mut tunables);
+                            self.game.move_speed = tunables.move_speed;
+                            self.game.rot_speed = tunables.rot_speed;
+                            self.game.fov = tunables.fov;
+                            self.game.ceiling = tunables.ceiling;
+                            self.game.floor = tunables.floor;
+                        }
                         buffer.present().expect("Failed to present");
                     }
                 }
@@ -123,10 +191,21 @@ impl ApplicationHandler for App {
         }
     }
     
+    fn device_event(&mut self, _event_loop: &ActiveEventLoop, _id: DeviceId, event: DeviceEvent) {
+        // Raw relative motion: not clamped by the window edges, unlike
+        // CursorMoved. Only steer while the pointer is grabbed.
+        if let DeviceEvent::MouseMotion { delta } = event {
+            if self.grabbed {
+                self.game.mouse_dx += delta.0 as f32;
+            }
+        }
+    }
+
     fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
         let now = Instant::now();
         let dt = now.duration_since(self.last_time).as_secs_f64();
         self.last_time = now;
+        self.frame_ms = (dt * 1000.0) as f32;
         self.accumulator += dt;
         while self.accumulator >= FIXED_DT {
             self.game.update(FIXED_DT as f32);
@@ -139,11 +218,51 @@ impl ApplicationHandler for App {
 #[derive(Default)]
 struct Input { forward: bool, backward: bool, left: bool, right: bool }
 
+/// A 64x64 ARGB wall texture.
+struct Texture { data: Vec<u32> }
+
+impl Texture {
+    /// Procedural brick/panel fallback so the demo runs without asset files.
+    fn procedural(base: u32) -> Self {
+        let mut data = vec![0u32; TEX_SIZE * TEX_SIZE];
+        for y in 0..TEX_SIZE {
+            for x in 0..TEX_SIZE {
+                // Mortar lines on a brick grid (alternate rows offset by half a
+                // brick), with a fine per-texel checker shading the bricks.
+                let mortar = y % 16 == 0 || (x + (y / 16 % 2) * 8) % 16 == 0;
+                let shade = if mortar { 0.4 } else { 0.85 + 0.15 * ((x ^ y) & 1) as f32 };
+                data[y * TEX_SIZE + x] = shade_color(base, shade);
+            }
+        }
+        Self { data }
+    }
+}
+
+/// Multiply an ARGB color's RGB channels by `factor`.
+fn shade_color(color: u32, factor: f32) -> u32 {
+    let r = (((color >> 16) & 0xFF) as f32 * factor) as u32;
+    let g = (((color >> 8) & 0xFF) as f32 * factor) as u32;
+    let b = ((color & 0xFF) as f32 * factor) as u32;
+    (r << 16) | (g << 8) | b
+}
+
 struct Game {
     pos_x: f32, pos_y: f32,
     dir_x: f32, dir_y: f32,
     plane_x: f32, plane_y: f32,
     input: Input,
+    /// Accumulated raw horizontal mouse motion, consumed each update.
+    mouse_dx: f32,
+    sensitivity: f32,
+    invert: bool,
+    // Live-tunable parameters (edited by the dev overlay).
+    move_speed: f32,
+    rot_speed: f32,
+    fov: f32,
+    ceiling: u32,
+    floor: u32,
+    /// Wall textures indexed by MAP cell value (index 0 is an unused fallback).
+    textures: Vec<Texture>,
 }
 
 impl Game {
@@ -153,13 +272,76 @@ impl Game {
             dir_x: 1.0, dir_y: 0.0,
             plane_x: 0.0, plane_y: 0.66,
             input: Input::default(),
+            mouse_dx: 0.0,
+            sensitivity: 0.0025,
+            invert: false,
+            move_speed: 3.0,
+            rot_speed: 2.0,
+            fov: 0.66,
+            ceiling: 0x00404060,
+            floor: 0x00505050,
+            textures: vec![
+                Texture::procedural(0x00FFFFFF), // 0: fallback
+                Texture::procedural(0x00FF0000), // 1: red brick
+                Texture::procedural(0x0000FF00), // 2: green panel
+                Texture::procedural(0x000000FF), // 3: blue panel
+            ],
         }
     }
-    
+
+    /// Replace the wall textures by decoding PNGs (cell value -> file path).
+    /// Decoded images are resized to 64x64 nearest-neighbour.
+    #[allow(dead_code)]
+    fn load_textures(&mut self, paths: &[&str]) {
+        for (cell, path) in paths.iter().enumerate() {
+            let img = match image::open(path) {
+                Ok(img) => img.to_rgba8(),
+                Err(e) => {
+                    log::warn!("texture {path}: {e}");
+                    continue;
+                }
+            };
+            let (iw, ih) = img.dimensions();
+            let mut data = vec![0u32; TEX_SIZE * TEX_SIZE];
+            for y in 0..TEX_SIZE {
+                for x in 0..TEX_SIZE {
+                    let sx = (x as u32 * iw / TEX_SIZE as u32).min(iw - 1);
+                    let sy = (y as u32 * ih / TEX_SIZE as u32).min(ih - 1);
+                    let px = img.get_pixel(sx, sy).0;
+                    data[y * TEX_SIZE + x] =
+                        ((px[0] as u32) << 16) | ((px[1] as u32) << 8) | px[2] as u32;
+                }
+            }
+            if cell < self.textures.len() {
+                self.textures[cell] = Texture { data };
+            } else {
+                self.textures.push(Texture { data });
+            }
+        }
+    }
+
+    /// Rotate the view basis (dir + plane) by `angle` radians.
+    fn rotate(&mut self, angle: f32) {
+        let (s, c) = angle.sin_cos();
+        let old_dir_x = self.dir_x;
+        self.dir_x = self.dir_x * c - self.dir_y * s;
+        self.dir_y = old_dir_x * s + self.dir_y * c;
+        let old_plane_x = self.plane_x;
+        self.plane_x = self.plane_x * c - self.plane_y * s;
+        self.plane_y = old_plane_x * s + self.plane_y * c;
+    }
+
     fn update(&mut self, dt: f32) {
-        let move_speed = 3.0 * dt;
-        let rot_speed = 2.0 * dt;
-        
+        let move_speed = self.move_speed * dt;
+        let rot_speed = self.rot_speed * dt;
+
+        // Mouse-look: yaw by accumulated horizontal delta, then clear it.
+        if self.mouse_dx != 0.0 {
+            let sign = if self.invert { 1.0 } else { -1.0 };
+            self.rotate(sign * self.mouse_dx * self.sensitivity);
+            self.mouse_dx = 0.0;
+        }
+
         if self.input.forward {
             let nx = self.pos_x + self.dir_x * move_speed;
             let ny = self.pos_y + self.dir_y * move_speed;
@@ -171,20 +353,17 @@ impl Game {
             if MAP[ny as usize * MAP_WIDTH + nx as usize] == 0 { self.pos_x = nx; self.pos_y = ny; }
         }
         if self.input.right {
-            let old_dir_x = self.dir_x;
-            self.dir_x = self.dir_x * (-rot_speed).cos() - self.dir_y * (-rot_speed).sin();
-            self.dir_y = old_dir_x * (-rot_speed).sin() + self.dir_y * (-rot_speed).cos();
-            let old_plane_x = self.plane_x;
-            self.plane_x = self.plane_x * (-rot_speed).cos() - self.plane_y * (-rot_speed).sin();
-            self.plane_y = old_plane_x * (-rot_speed).sin() + self.plane_y * (-rot_speed).cos();
+            self.rotate(-rot_speed);
         }
         if self.input.left {
-            let old_dir_x = self.dir_x;
-            self.dir_x = self.dir_x * rot_speed.cos() - self.dir_y * rot_speed.sin();
-            self.dir_y = old_dir_x * rot_speed.sin() + self.dir_y * rot_speed.cos();
-            let old_plane_x = self.plane_x;
-            self.plane_x = self.plane_x * rot_speed.cos() - self.plane_y * rot_speed.sin();
-            self.plane_y = old_plane_x * rot_speed.sin() + self.plane_y * rot_speed.cos();
+            self.rotate(rot_speed);
+        }
+
+        // Keep the camera plane perpendicular to the view with the current FOV.
+        let len = (self.dir_x * self.dir_x + self.dir_y * self.dir_y).sqrt();
+        if len > 0.0 {
+            self.plane_x = -self.dir_y / len * self.fov;
+            self.plane_y = self.dir_x / len * self.fov;
         }
     }
     
@@ -192,7 +371,7 @@ impl Game {
         let half_h = height / 2;
         // Clear - ceiling and floor
         for y in 0..height as usize {
-            let color = if y < half_h as usize { 0x00404060 } else { 0x00505050 };
+            let color = if y < half_h as usize { self.ceiling } else { self.floor };
             for x in 0..width as usize {
                 buffer[y * width as usize + x] = color;
             }
@@ -248,16 +427,31 @@ impl Game {
             let line_height = if perp_dist > 0.0 { (height as f32 / perp_dist) as i32 } else { height as i32 };
             let draw_start = (-line_height / 2 + half_h as i32).max(0) as usize;
             let draw_end = (line_height / 2 + half_h as i32).min(height as i32 - 1) as usize;
-            
-            let color = match hit {
-                1 => if side == 1 { 0x00AA0000 } else { 0x00FF0000 },
-                2 => if side == 1 { 0x0000AA00 } else { 0x0000FF00 },
-                3 => if side == 1 { 0x000000AA } else { 0x000000FF },
-                _ => 0x00FFFFFF,
+
+            // Exact hit coordinate along the wall -> horizontal texture U.
+            let mut wall_x = if side == 0 {
+                self.pos_y + perp_dist * ray_dir_y
+            } else {
+                self.pos_x + perp_dist * ray_dir_x
             };
-            
+            wall_x -= wall_x.floor();
+            let tex = &self.textures[(hit as usize).min(self.textures.len() - 1)];
+            let mut tex_u = (wall_x * TEX_SIZE as f32) as i32;
+            // Flip U so textures aren't mirrored on opposite-facing walls.
+            if (side == 0 && ray_dir_x > 0.0) || (side == 1 && ray_dir_y < 0.0) {
+                tex_u = TEX_SIZE as i32 - tex_u - 1;
+            }
+            let tex_u = tex_u.clamp(0, TEX_SIZE as i32 - 1) as usize;
+
+            // Side-based darkening as a multiplicative shade.
+            let shade = if side == 1 { 0.7 } else { 1.0 };
+
             for y in draw_start..=draw_end {
-                buffer[y * width as usize + x as usize] = color;
+                let tex_v = (((y as i32 * 2 - height as i32 + line_height) * TEX_SIZE as i32)
+                    / (line_height * 2))
+                    .clamp(0, TEX_SIZE as i32 - 1) as usize;
+                let texel = tex.data[tex_v * TEX_SIZE + tex_u];
+                buffer[y * width as usize + x as usize] = shade_color(texel, shade);
             }
         }
         
@@ -0,0 +1,302 @@
+//! Evolvable neural-network steering agents
+//!
+//! An optional AI subsystem where an agent is driven by a small feed-forward
+//! neural network, plus a genetic trainer that evolves a population over
+//! generations. It is used by the massive-sim demo to replace the hand-rolled
+//! position-hash steering with learned behavior.
+//!
+//! Determinism is preserved by driving every random draw — weight init and
+//! mutation — from a seeded RNG, so a given seed reproduces the same evolved
+//! population.
+
+/// Seeded RNG producing uniforms and standard-normal samples.
+///
+/// A 32-bit xorshift feeds a Box–Muller transform for the normal draws used by
+/// He initialization and mutation.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u32,
+}
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Self { state: seed | 1 }
+    }
+
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Uniform in `[0, 1)`.
+    #[inline]
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Standard-normal sample via Box–Muller.
+    pub fn randn(&mut self) -> f32 {
+        let u1 = self.next_f32().max(1e-7);
+        let u2 = self.next_f32();
+        (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
+    }
+}
+
+/// Nonlinearity applied to hidden layers during the forward pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Activation {
+    ReLU,
+    Tanh,
+    Sigmoid,
+}
+
+impl Activation {
+    #[inline]
+    fn apply(self, x: f32) -> f32 {
+        match self {
+            Activation::ReLU => x.max(0.0),
+            Activation::Tanh => x.tanh(),
+            Activation::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+        }
+    }
+}
+
+/// Steering intents produced by a network, each clamped to `[-1, 1]`; callers
+/// map them onto input booleans or directly onto `vx`/`vy`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Controls {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A feed-forward network: one weight matrix per layer transition.
+///
+/// `config` is `[inputs, hidden.., outputs]`. Each matrix has shape
+/// `(next_layer, prev_layer + 1)`, the trailing column folding in the bias.
+#[derive(Clone, Debug)]
+pub struct Network {
+    pub config: Vec<usize>,
+    /// Row-major weight matrices, one per layer transition.
+    pub weights: Vec<Vec<f32>>,
+    /// Hidden-layer nonlinearity (outputs are always returned raw).
+    pub activation: Activation,
+}
+
+impl Network {
+    /// Build a network with He-initialized weights (`randn * sqrt(2/fan_in)`)
+    /// and ReLU hidden activations.
+    pub fn new(config: Vec<usize>, rng: &mut Rng) -> Self {
+        let mut weights = Vec::with_capacity(config.len().saturating_sub(1));
+        for w in config.windows(2) {
+            let (prev, next) = (w[0], w[1]);
+            let scale = (2.0 / prev as f32).sqrt();
+            let mut matrix = vec![0.0; next * (prev + 1)];
+            for weight in matrix.iter_mut() {
+                *weight = rng.randn() * scale;
+            }
+            weights.push(matrix);
+        }
+        Self { config, weights, activation: Activation::ReLU }
+    }
+
+    /// Builder override for the hidden-layer activation.
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Forward propagate, applying the network's activation (ReLU unless set
+    /// via [`with_activation`](Self::with_activation)) to hidden layers. Outputs are returned
+    /// raw (interpreted as desired acceleration x/y by callers).
+    pub fn forward(&self, inputs: &[f32]) -> Vec<f32> {
+        let mut activations = inputs.to_vec();
+        let last = self.weights.len() - 1;
+        for (layer, matrix) in self.weights.iter().enumerate() {
+            let prev = self.config[layer];
+            let next = self.config[layer + 1];
+            let mut out = vec![0.0; next];
+            for (row, o) in out.iter_mut().enumerate() {
+                let base = row * (prev + 1);
+                let mut sum = matrix[base + prev]; // bias column
+                for col in 0..prev {
+                    sum += matrix[base + col] * activations[col];
+                }
+                // Nonlinearity on hidden layers only; outputs stay raw.
+                *o = if layer < last { self.activation.apply(sum) } else { sum };
+            }
+            activations = out;
+        }
+        activations
+    }
+
+    /// Run the network and read the first two outputs as steering intents.
+    pub fn steer(&self, sensors: &[f32]) -> Controls {
+        let out = self.forward(sensors);
+        Controls {
+            x: out.first().copied().unwrap_or(0.0).clamp(-1.0, 1.0),
+            y: out.get(1).copied().unwrap_or(0.0).clamp(-1.0, 1.0),
+        }
+    }
+
+    /// Produce a mutated clone: each weight is resampled from a standard normal
+    /// with probability `mut_rate`.
+    pub fn mutated(&self, mut_rate: f32, rng: &mut Rng) -> Network {
+        let mut child = self.clone();
+        for matrix in child.weights.iter_mut() {
+            for weight in matrix.iter_mut() {
+                if rng.next_f32() < mut_rate {
+                    *weight = rng.randn();
+                }
+            }
+        }
+        child
+    }
+}
+
+impl Network {
+    /// Serialize the genome (layer config + all weights) to a little-endian
+    /// byte buffer so a trained brain can be reloaded without retraining.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(self.config.len() as u32).to_le_bytes());
+        for &layer in &self.config {
+            bytes.extend_from_slice(&(layer as u32).to_le_bytes());
+        }
+        bytes.push(self.activation as u8);
+        for matrix in &self.weights {
+            for &w in matrix {
+                bytes.extend_from_slice(&w.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Reconstruct a network from [`to_bytes`](Self::to_bytes). Returns `None`
+    /// if the buffer is truncated or malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Network> {
+        let mut cursor = 0;
+        let read_u32 = |cursor: &mut usize| -> Option<u32> {
+            let v = bytes.get(*cursor..*cursor + 4)?;
+            *cursor += 4;
+            Some(u32::from_le_bytes(v.try_into().ok()?))
+        };
+
+        let layers = read_u32(&mut cursor)? as usize;
+        let mut config = Vec::with_capacity(layers);
+        for _ in 0..layers {
+            config.push(read_u32(&mut cursor)? as usize);
+        }
+        let activation = match bytes.get(cursor)? {
+            0 => Activation::ReLU,
+            1 => Activation::Tanh,
+            _ => Activation::Sigmoid,
+        };
+        cursor += 1;
+
+        let mut weights = Vec::with_capacity(config.len().saturating_sub(1));
+        for w in config.windows(2) {
+            let len = w[1] * (w[0] + 1);
+            let mut matrix = Vec::with_capacity(len);
+            for _ in 0..len {
+                let v = bytes.get(cursor..cursor + 4)?;
+                cursor += 4;
+                matrix.push(f32::from_le_bytes(v.try_into().ok()?));
+            }
+            weights.push(matrix);
+        }
+        Some(Network { config, weights, activation })
+    }
+}
+
+/// A network bound to one entity, exposing the ECS-facing `think` call. The
+/// controller owns no state beyond its brain, so it is cheap to clone when a
+/// genome is swapped in from a new generation.
+#[derive(Clone, Debug)]
+pub struct NeuralController {
+    pub net: Network,
+}
+
+impl NeuralController {
+    pub fn new(net: Network) -> Self {
+        Self { net }
+    }
+
+    /// Forward the sensor vector through the brain, returning the raw output
+    /// vector (interpreted as desired linear acceleration by the caller).
+    pub fn think(&self, inputs: &[f32]) -> Vec<f32> {
+        self.net.forward(inputs)
+    }
+}
+
+/// Breed a child by picking each weight from either parent with equal
+/// probability. Both parents must share the same `config`; the child inherits
+/// the first parent's activation.
+pub fn crossover(a: &Network, b: &Network, rng: &mut Rng) -> Network {
+    let mut child = a.clone();
+    for (cm, bm) in child.weights.iter_mut().zip(&b.weights) {
+        for (w, &bw) in cm.iter_mut().zip(bm) {
+            if rng.next_u32() & 1 == 1 {
+                *w = bw;
+            }
+        }
+    }
+    child
+}
+
+/// A population of networks evolved by a genetic algorithm.
+pub struct Population {
+    pub agents: Vec<Network>,
+    /// Fraction of top performers kept each generation.
+    pub elite_fraction: f32,
+    pub mut_rate: f32,
+    pub generation: u32,
+    rng: Rng,
+}
+
+impl Population {
+    pub fn new(size: usize, config: Vec<usize>, seed: u32) -> Self {
+        let mut rng = Rng::new(seed);
+        let agents = (0..size).map(|_| Network::new(config.clone(), &mut rng)).collect();
+        Self { agents, elite_fraction: 0.25, mut_rate: 0.02, generation: 0, rng }
+    }
+
+    /// Breed the next generation: keep the top `elite_fraction` by fitness and
+    /// refill with crossover children of survivor pairs, each mutated.
+    pub fn evolve(&mut self, fitness: &[f32]) {
+        assert_eq!(fitness.len(), self.agents.len());
+
+        // Rank agents by fitness (descending); index-sorted for determinism.
+        let mut order: Vec<usize> = (0..self.agents.len()).collect();
+        order.sort_by(|&a, &b| {
+            fitness[b].partial_cmp(&fitness[a]).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let keep = ((self.agents.len() as f32 * self.elite_fraction) as usize).max(1);
+        let survivors: Vec<Network> = order[..keep].iter().map(|&i| self.agents[i].clone()).collect();
+
+        let mut next = survivors.clone();
+        let mut s = 0;
+        while next.len() < self.agents.len() {
+            let a = &survivors[s % survivors.len()];
+            let b = &survivors[(s + 1) % survivors.len()];
+            let child = crossover(a, b, &mut self.rng).mutated(self.mut_rate, &mut self.rng);
+            next.push(child);
+            s += 1;
+        }
+
+        self.agents = next;
+        self.generation += 1;
+    }
+
+    /// The best genome after the most recent [`evolve`](Self::evolve): survivors
+    /// are placed first, so `agents[0]` is the top performer. Useful for
+    /// serializing a trained brain with [`Network::to_bytes`].
+    pub fn best(&self) -> Option<&Network> {
+        self.agents.first()
+    }
+}
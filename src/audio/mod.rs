@@ -1,35 +1,239 @@
-//! Audio module (placeholder)
-//! 
-//! Rust handles audio API. No ASM needed for audio.
+//! Audio module
+//!
+//! Rust handles the audio API; playback is backed by `rodio`/`cpal`. One-shot
+//! sound effects are played by id, while background music is *streamed* from
+//! disk (decoded on the fly via [`rodio::Decoder`]) so large compressed tracks
+//! never sit fully decoded in RAM.
+//!
+//! Music is organized into named *soundtracks*. Each soundtrack maps the same
+//! logical song ids to different files, so a caller can swap the whole set at
+//! runtime (e.g. `"original"` vs `"remastered"`) while gameplay keeps calling
+//! `play_music(id)` with stable ids. Switching songs crossfades in lockstep
+//! with the deterministic loop: [`AudioSystem::tick`] is called once per fixed
+//! update to ramp the outgoing track down while the incoming one ramps up.
 
-/// Audio system placeholder
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+
+/// Logical song id, stable across soundtracks.
+pub type TrackId = usize;
+
+/// A named set of music files indexed by [`TrackId`].
+#[derive(Clone, Debug, Default)]
+pub struct Soundtrack {
+    /// `music_table[id]` is the file backing logical song `id`.
+    pub music_table: Vec<PathBuf>,
+}
+
+impl Soundtrack {
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        Self { music_table: files }
+    }
+
+    fn resolve(&self, id: TrackId) -> Option<&Path> {
+        self.music_table.get(id).map(|p| p.as_path())
+    }
+}
+
+/// A music track currently fading in or out.
+struct FadingTrack {
+    sink: Sink,
+    /// Ramp progress in fixed ticks.
+    ticks: u32,
+    /// Total length of the ramp in fixed ticks.
+    fade_ticks: u32,
+    /// `true` while fading out (volume ramps to zero, then the sink stops).
+    fading_out: bool,
+}
+
+/// Audio system backed by `rodio`.
 pub struct AudioSystem {
     enabled: bool,
+    master_volume: f32,
+    crossfade_ticks: u32,
+    soundtracks: HashMap<String, Soundtrack>,
+    current_soundtrack: Option<String>,
+    _stream: Option<OutputStream>,
+    handle: Option<OutputStreamHandle>,
+    /// One-shot SFX sinks, retained until they finish.
+    sfx: Vec<Sink>,
+    /// The track fading in (becomes the steady track once its ramp completes).
+    music_in: Option<FadingTrack>,
+    /// The previous track fading out.
+    music_out: Option<FadingTrack>,
 }
 
 impl AudioSystem {
     pub fn new() -> Self {
-        Self { enabled: true }
+        // Opening the default device can fail on headless hosts; degrade to a
+        // silent (disabled) system rather than panicking.
+        let (stream, handle) = match OutputStream::try_default() {
+            Ok((s, h)) => (Some(s), Some(h)),
+            Err(err) => {
+                log::warn!("audio device unavailable, running silent: {err}");
+                (None, None)
+            }
+        };
+
+        Self {
+            enabled: handle.is_some(),
+            master_volume: 1.0,
+            crossfade_ticks: 30,
+            soundtracks: HashMap::new(),
+            current_soundtrack: None,
+            _stream: stream,
+            handle,
+            sfx: Vec::new(),
+            music_in: None,
+            music_out: None,
+        }
     }
-    
-    pub fn play_sound(&self, _id: u32) {
-        // TODO: Implement with rodio or similar
+
+    /// Register (or replace) a named soundtrack.
+    pub fn register_soundtrack(&mut self, name: impl Into<String>, soundtrack: Soundtrack) {
+        let name = name.into();
+        if self.current_soundtrack.is_none() {
+            self.current_soundtrack = Some(name.clone());
+        }
+        self.soundtracks.insert(name, soundtrack);
     }
-    
-    pub fn play_music(&self, _id: u32) {
-        // TODO: Implement
+
+    /// Switch the active soundtrack set. Logical song ids are preserved, so a
+    /// currently playing song can be re-resolved to the new set by the caller.
+    pub fn set_soundtrack(&mut self, name: &str) -> bool {
+        if self.soundtracks.contains_key(name) {
+            self.current_soundtrack = Some(name.to_string());
+            true
+        } else {
+            false
+        }
     }
-    
-    pub fn stop_music(&self) {
-        // TODO: Implement
+
+    /// Number of fixed ticks a music crossfade takes.
+    pub fn set_crossfade_ticks(&mut self, ticks: u32) {
+        self.crossfade_ticks = ticks.max(1);
     }
-    
-    pub fn set_volume(&mut self, _volume: f32) {
-        // TODO: Implement
+
+    /// Play a one-shot sound effect from `path`.
+    pub fn play_sound(&mut self, path: impl AsRef<Path>) {
+        if !self.enabled {
+            return;
+        }
+        if let Some(sink) = self.open_sink(path.as_ref(), false) {
+            sink.set_volume(self.master_volume);
+            self.sfx.push(sink);
+        }
+        // Reap finished one-shots so the vec doesn't grow unbounded.
+        self.sfx.retain(|s| !s.empty());
+    }
+
+    /// Start streaming logical song `id` from the active soundtrack, crossfading
+    /// from whatever is currently playing.
+    pub fn play_music(&mut self, id: TrackId) {
+        if !self.enabled {
+            return;
+        }
+        let path = match self
+            .current_soundtrack
+            .as_ref()
+            .and_then(|name| self.soundtracks.get(name))
+            .and_then(|st| st.resolve(id))
+        {
+            Some(p) => p.to_path_buf(),
+            None => {
+                log::warn!("no music registered for track id {id}");
+                return;
+            }
+        };
+
+        let sink = match self.open_sink(&path, true) {
+            Some(s) => s,
+            None => return,
+        };
+        sink.set_volume(0.0);
+
+        // Push the current incoming track out, and fade in the new one.
+        if let Some(prev) = self.music_in.take() {
+            self.music_out = Some(FadingTrack { fading_out: true, ..prev });
+        }
+        self.music_in = Some(FadingTrack {
+            sink,
+            ticks: 0,
+            fade_ticks: self.crossfade_ticks,
+            fading_out: false,
+        });
+    }
+
+    /// Stop background music immediately.
+    pub fn stop_music(&mut self) {
+        self.music_in = None;
+        self.music_out = None;
     }
-    
+
+    /// Master volume in `[0, 1]`.
+    pub fn set_volume(&mut self, volume: f32) {
+        self.master_volume = volume.clamp(0.0, 1.0);
+    }
+
     pub fn set_enabled(&mut self, enabled: bool) {
-        self.enabled = enabled;
+        self.enabled = enabled && self.handle.is_some();
+    }
+
+    /// Advance crossfades by one fixed tick. Call once per fixed update so the
+    /// ramp stays in step with the deterministic loop.
+    pub fn tick(&mut self) {
+        if let Some(track) = self.music_in.as_mut() {
+            track.ticks = (track.ticks + 1).min(track.fade_ticks);
+            let t = track.ticks as f32 / track.fade_ticks as f32;
+            track.sink.set_volume(self.master_volume * t);
+        }
+
+        if let Some(track) = self.music_out.as_mut() {
+            debug_assert!(track.fading_out);
+            track.ticks = (track.ticks + 1).min(track.fade_ticks);
+            let t = 1.0 - track.ticks as f32 / track.fade_ticks as f32;
+            track.sink.set_volume(self.master_volume * t);
+            if track.ticks >= track.fade_ticks {
+                track.sink.stop();
+                self.music_out = None;
+            }
+        }
+    }
+
+    /// Decode and queue a source, looping it when `looping` is set (music).
+    fn open_sink(&self, path: &Path, looping: bool) -> Option<Sink> {
+        let handle = self.handle.as_ref()?;
+        let file = match File::open(path) {
+            Ok(f) => BufReader::new(f),
+            Err(err) => {
+                log::warn!("failed to open audio file {path:?}: {err}");
+                return None;
+            }
+        };
+        let decoder = match Decoder::new(file) {
+            Ok(d) => d,
+            Err(err) => {
+                log::warn!("failed to decode {path:?}: {err}");
+                return None;
+            }
+        };
+        let sink = match Sink::try_new(handle) {
+            Ok(s) => s,
+            Err(err) => {
+                log::warn!("failed to create audio sink: {err}");
+                return None;
+            }
+        };
+        if looping {
+            sink.append(decoder.repeat_infinite());
+        } else {
+            sink.append(decoder);
+        }
+        Some(sink)
     }
 }
 
@@ -0,0 +1,135 @@
+//! Data-driven level and entity definitions loaded from TOML.
+//!
+//! The raycaster map and the massive-sim entity spawn used to be baked into the
+//! game source. A [`LevelDef`] lifts them into a `.toml` file so new maps ship
+//! without recompiling; it carries an optional [`RaycasterLevel`] and/or
+//! [`SimLevel`], each keyed as a table. The game constructors
+//! (`RaycasterGame::from_file` / `MassiveSimGame::from_file`) validate the
+//! definition and build from it.
+//!
+//! Deserialization lives behind the optional `serde` feature, matching the rest
+//! of the engine's content-facing types; [`RaycasterLevel::validate`] is always
+//! available so a hand-built definition can be checked too.
+
+/// Errors surfaced while loading or validating a level definition.
+#[derive(Debug)]
+pub enum ContentError {
+    Io(std::io::Error),
+    Parse(String),
+    /// Tile grid length did not equal `width * height`.
+    TileCountMismatch { expected: usize, found: usize },
+    /// The player start position lies outside the map or inside a wall.
+    StartBlocked,
+    /// The file lacked a section a constructor required.
+    MissingSection(&'static str),
+}
+
+impl From<std::io::Error> for ContentError {
+    fn from(e: std::io::Error) -> Self {
+        ContentError::Io(e)
+    }
+}
+
+/// A parsed level file: any combination of the supported game sections.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LevelDef {
+    pub raycaster: Option<RaycasterLevel>,
+    pub sim: Option<SimLevel>,
+}
+
+/// A raycaster map: tile grid plus the camera's starting pose.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RaycasterLevel {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major cell grid (`0` = empty, nonzero = wall value).
+    pub tiles: Vec<u8>,
+    /// Player start position, in map cells.
+    pub start: [f32; 2],
+    /// Initial facing direction.
+    pub direction: [f32; 2],
+    /// Flat ARGB colors keyed by nonzero cell value, applied as 1x1 wall
+    /// textures through the existing texture path.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wall_colors: Vec<WallColor>,
+}
+
+/// A flat wall color assignment for one map cell value.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WallColor {
+    pub cell: u8,
+    pub color: u32,
+}
+
+impl RaycasterLevel {
+    /// Check the tile count matches `width * height` and the start cell is
+    /// inside the map and empty.
+    pub fn validate(&self) -> Result<(), ContentError> {
+        let expected = (self.width as usize) * (self.height as usize);
+        if self.tiles.len() != expected {
+            return Err(ContentError::TileCountMismatch {
+                expected,
+                found: self.tiles.len(),
+            });
+        }
+        let sx = self.start[0] as i32;
+        let sy = self.start[1] as i32;
+        if sx < 0 || sy < 0 || sx >= self.width as i32 || sy >= self.height as i32 {
+            return Err(ContentError::StartBlocked);
+        }
+        let idx = sy as usize * self.width as usize + sx as usize;
+        if self.tiles[idx] != 0 {
+            return Err(ContentError::StartBlocked);
+        }
+        Ok(())
+    }
+}
+
+/// A massive-sim spawn: how many entities, how they look, and the world they
+/// wrap around.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimLevel {
+    pub entity_count: usize,
+    /// World bounds `[width, height]` the entities wrap inside.
+    pub world: [f32; 2],
+    /// ARGB palette cycled across the spawned entities.
+    pub palette: Vec<u32>,
+    /// Inclusive radius range entities are spawned within.
+    pub radius_min: f32,
+    pub radius_max: f32,
+    /// Optional Boids tuning; the engine defaults are used when absent.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub boids: Option<BoidTuning>,
+}
+
+/// Boids weights and radii as they appear in a level file; mapped onto the
+/// game's own `BoidParams` at construction.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoidTuning {
+    pub perception_radius: f32,
+    pub separation_radius: f32,
+    pub sep_weight: f32,
+    pub align_weight: f32,
+    pub cohesion_weight: f32,
+    pub max_speed: f32,
+}
+
+#[cfg(feature = "serde")]
+impl LevelDef {
+    /// Parse a level definition from a TOML string.
+    pub fn from_toml(source: &str) -> Result<Self, ContentError> {
+        toml::from_str(source).map_err(|e| ContentError::Parse(e.to_string()))
+    }
+
+    /// Read and parse a level definition from a TOML file.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, ContentError> {
+        let source = std::fs::read_to_string(path)?;
+        Self::from_toml(&source)
+    }
+}
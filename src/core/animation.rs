@@ -0,0 +1,142 @@
+//! Frame-count-driven tween/easing subsystem
+//!
+//! Tweens interpolate a value over a fixed number of simulation ticks rather
+//! than wall-clock time, so they are deterministic and survive replay/rollback.
+//! Given the current `tick` from [`FrameTick`](super::FrameTick), a [`Tween`]
+//! reports the interpolated value, holding at the end (or looping) once the
+//! span elapses.
+
+use crate::math::Vec2;
+
+/// Values a [`Tween`] can interpolate.
+///
+/// Implemented for `f32`, [`Vec2`], and RGBA color channels (`[f32; 4]`) so
+/// both entity positions and the renderer's colors can be animated.
+pub trait Tweenable: Copy {
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn scale(self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    #[inline]
+    fn add(self, other: Self) -> Self { self + other }
+    #[inline]
+    fn sub(self, other: Self) -> Self { self - other }
+    #[inline]
+    fn scale(self, t: f32) -> Self { self * t }
+}
+
+impl Tweenable for Vec2 {
+    #[inline]
+    fn add(self, other: Self) -> Self { self + other }
+    #[inline]
+    fn sub(self, other: Self) -> Self { self - other }
+    #[inline]
+    fn scale(self, t: f32) -> Self { self * t }
+}
+
+impl Tweenable for [f32; 4] {
+    #[inline]
+    fn add(self, o: Self) -> Self { [self[0] + o[0], self[1] + o[1], self[2] + o[2], self[3] + o[3]] }
+    #[inline]
+    fn sub(self, o: Self) -> Self { [self[0] - o[0], self[1] - o[1], self[2] - o[2], self[3] - o[3]] }
+    #[inline]
+    fn scale(self, t: f32) -> Self { [self[0] * t, self[1] * t, self[2] * t, self[3] * t] }
+}
+
+/// Easing curves applied to the normalized progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseInQuad,
+    EaseOutQuad,
+    EaseInOutQuad,
+}
+
+impl Easing {
+    /// Map normalized progress `t` in `[0, 1]` through the curve.
+    #[inline]
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutQuad => t * (2.0 - t),
+            Easing::EaseInOutQuad => {
+                if t < 0.5 { 2.0 * t * t } else { -1.0 + (4.0 - 2.0 * t) * t }
+            }
+        }
+    }
+}
+
+/// How a tween behaves once its frame span elapses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoopMode {
+    /// Hold at the end value.
+    Clamp,
+    /// Restart from the beginning.
+    Loop,
+    /// Play forward then backward.
+    PingPong,
+}
+
+/// Interpolates a [`Tweenable`] value across a fixed number of ticks.
+#[derive(Clone, Copy, Debug)]
+pub struct Tween<T: Tweenable> {
+    start: T,
+    range: T,
+    /// Per-tick slope, precomputed once for the linear fast path.
+    slope: T,
+    frames: u64,
+    start_tick: u64,
+    easing: Easing,
+    loop_mode: LoopMode,
+}
+
+impl<T: Tweenable> Tween<T> {
+    /// Create a tween running from `start` to `end` over `frames` ticks,
+    /// beginning at `start_tick`. A zero `frames` is treated as one to avoid
+    /// division by zero (the value snaps straight to `end`).
+    pub fn new(start: T, end: T, frames: u64, start_tick: u64, easing: Easing) -> Self {
+        let frames = frames.max(1);
+        let range = end.sub(start);
+        let slope = range.scale(1.0 / frames as f32);
+        Self { start, range, slope, frames, start_tick, easing, loop_mode: LoopMode::Clamp }
+    }
+
+    /// Set the loop behavior (builder style).
+    pub fn with_loop(mut self, loop_mode: LoopMode) -> Self {
+        self.loop_mode = loop_mode;
+        self
+    }
+
+    /// Sample the tween at simulation `tick`.
+    pub fn value(&self, tick: u64) -> T {
+        let elapsed = tick.saturating_sub(self.start_tick);
+
+        let frames = self.frames;
+        let eased_frames = match self.loop_mode {
+            LoopMode::Clamp => elapsed.min(frames),
+            LoopMode::Loop => elapsed % frames,
+            LoopMode::PingPong => {
+                let p = elapsed % (2 * frames);
+                if p <= frames { p } else { 2 * frames - p }
+            }
+        };
+
+        match self.easing {
+            // Linear: step along the precomputed per-tick slope.
+            Easing::Linear => self.start.add(self.slope.scale(eased_frames as f32)),
+            // Non-linear: remap through the easing curve.
+            _ => {
+                let t = self.easing.apply(eased_frames as f32 / frames as f32);
+                self.start.add(self.range.scale(t))
+            }
+        }
+    }
+
+    /// Whether a `Clamp` tween has reached its end at `tick`.
+    pub fn is_finished(&self, tick: u64) -> bool {
+        self.loop_mode == LoopMode::Clamp && tick.saturating_sub(self.start_tick) >= self.frames
+    }
+}
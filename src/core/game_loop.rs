@@ -71,6 +71,71 @@ impl GameLoop {
     }
 }
 
+/// Deterministic integer-tick game loop
+///
+/// Where [`GameLoop`] accumulates elapsed time in `f64` — which drifts across
+/// CPUs/compilers — `FixedGameLoop` counts integer nanosecond ticks from
+/// [`Timer::elapsed_ns`] and derives the
+/// number of fixed updates by integer division against a fixed number of ticks
+/// per step. No floating accumulation means the simulation advances the exact
+/// same number of steps given the same tick stream on any target.
+pub struct FixedGameLoop {
+    timer: Timer,
+    last_ticks: u64,
+    accumulated_ticks: u64,
+    ticks_per_step: u64,
+    frame: u64,
+    tick: u64,
+}
+
+impl FixedGameLoop {
+    /// Build a loop that runs one fixed update every `ticks_per_step`
+    /// nanoseconds (e.g. 1_000_000_000 / target step rate).
+    pub fn new(ticks_per_step: u64) -> Self {
+        let mut timer = Timer::new();
+        timer.start();
+        Self {
+            timer,
+            last_ticks: 0,
+            accumulated_ticks: 0,
+            ticks_per_step: ticks_per_step.max(1),
+            frame: 0,
+            tick: 0,
+        }
+    }
+
+    /// Advance the loop by the elapsed nanoseconds, returning how many fixed
+    /// updates to run this frame (capped by `max_frame_skip`).
+    pub fn tick(&mut self, max_frame_skip: u32) -> FrameTick {
+        let now = self.timer.elapsed_ns();
+        let delta_ticks = now.saturating_sub(self.last_ticks);
+        self.last_ticks = now;
+        self.accumulated_ticks += delta_ticks;
+
+        let mut updates = 0u32;
+        while self.accumulated_ticks >= self.ticks_per_step && updates < max_frame_skip {
+            self.accumulated_ticks -= self.ticks_per_step;
+            self.tick += 1;
+            updates += 1;
+        }
+
+        self.frame += 1;
+
+        FrameTick {
+            frame: self.frame,
+            tick: self.tick,
+            fixed_updates: updates,
+            delta: delta_ticks as f64,
+            fixed_dt: self.ticks_per_step as f64,
+            interpolation: self.accumulated_ticks as f64 / self.ticks_per_step as f64,
+        }
+    }
+
+    pub fn tick_count(&self) -> u64 {
+        self.tick
+    }
+}
+
 /// Result of one game loop iteration
 #[derive(Clone, Copy, Debug)]
 pub struct FrameTick {
@@ -6,7 +6,10 @@
 pub mod timing;
 pub mod game_loop;
 pub mod profiler;
+pub mod animation;
+pub mod rollback;
 
 pub use timing::*;
 pub use game_loop::*;
 pub use profiler::*;
+pub use animation::*;
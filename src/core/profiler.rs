@@ -1,12 +1,60 @@
 //! Real-time profiler for performance monitoring
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
 use super::timing::Timer;
 
+/// Default number of per-frame totals retained for the on-screen graph.
+const DEFAULT_FRAME_HISTORY: usize = 120;
+
+/// Number of log-scale duration buckets kept per scope. Bucket `i` counts
+/// samples whose microsecond duration falls in `[2^i, 2^(i+1))`, with the last
+/// bucket absorbing everything larger.
+pub const HISTOGRAM_BUCKETS: usize = 12;
+
+/// A single timed span, captured for the Chrome tracing export.
+#[derive(Clone, Debug)]
+pub struct TraceEvent {
+    pub name: &'static str,
+    /// Nanosecond start offset from the profiler's epoch.
+    pub start_ns: u64,
+    pub dur_ns: u64,
+    pub thread: u64,
+    /// Nesting depth within the calling thread's scope stack (0 = top level).
+    pub depth: usize,
+}
+
+/// One entry in a [`Profiler::report`], pairing a scope name with its
+/// accumulated sample.
+#[derive(Clone, Debug)]
+pub struct ReportEntry {
+    pub name: &'static str,
+    pub sample: ProfileSample,
+}
+
 /// Performance profiler
 pub struct Profiler {
     samples: HashMap<&'static str, ProfileSample>,
     timer: Timer,
+    /// Per-frame event log, appended as scopes close and drained by export.
+    events: Vec<TraceEvent>,
+    /// Ring buffer of the last `frame_history` frame totals, in milliseconds.
+    frame_times: Vec<f64>,
+    frame_history: usize,
+    /// Wall-clock origin for trace timestamps and the current frame's start.
+    epoch: Instant,
+    frame_start: Instant,
+    /// Open scopes on this thread, tracking nesting depth and start offsets.
+    stack: Vec<OpenScope>,
+}
+
+/// A scope opened via [`Profiler::open`] and not yet closed.
+struct OpenScope {
+    name: &'static str,
+    start_ns: u64,
+    depth: usize,
 }
 
 #[derive(Default, Clone)]
@@ -15,6 +63,8 @@ pub struct ProfileSample {
     pub count: u64,
     pub min_ns: u64,
     pub max_ns: u64,
+    /// Rolling log-scale duration histogram; see [`HISTOGRAM_BUCKETS`].
+    pub histogram: [u64; HISTOGRAM_BUCKETS],
 }
 
 impl ProfileSample {
@@ -25,31 +75,119 @@ impl ProfileSample {
             0.0
         }
     }
+
+    /// Mean duration in nanoseconds, or `0` when no samples were recorded.
+    pub fn mean_ns(&self) -> u64 {
+        if self.count > 0 {
+            self.total_ns / self.count
+        } else {
+            0
+        }
+    }
+
+    /// Add one duration to the running min/max/count/histogram.
+    fn observe(&mut self, ns: u64) {
+        self.total_ns += ns;
+        self.count += 1;
+        self.min_ns = self.min_ns.min(ns);
+        self.max_ns = self.max_ns.max(ns);
+        let us = ns / 1000;
+        let bucket = (63 - (us | 1).leading_zeros()) as usize;
+        self.histogram[bucket.min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
 }
 
 impl Profiler {
     pub fn new() -> Self {
+        let now = Instant::now();
         Self {
             samples: HashMap::new(),
             timer: Timer::new(),
+            events: Vec::new(),
+            frame_times: Vec::new(),
+            frame_history: DEFAULT_FRAME_HISTORY,
+            epoch: now,
+            frame_start: now,
+            stack: Vec::new(),
         }
     }
-    
+
+    /// Open a timed scope whose duration is recorded when the returned guard is
+    /// dropped. The elapsed time feeds [`record`](Self::record) and a trace
+    /// event is appended to the per-frame log.
+    pub fn scope(&mut self, name: &'static str) -> ScopeGuard<'_> {
+        let start_ns = self.epoch.elapsed().as_nanos() as u64;
+        let mut timer = Timer::new();
+        timer.start();
+        ScopeGuard { profiler: self, name, start_ns, timer }
+    }
+
+    /// Mark the start of a frame for frame-total accounting.
+    pub fn begin_frame(&mut self) {
+        self.frame_start = Instant::now();
+    }
+
+    /// Close the current frame, pushing its total wall time (ms) onto the
+    /// bounded history ring.
+    pub fn end_frame(&mut self) {
+        let ms = self.frame_start.elapsed().as_secs_f64() * 1000.0;
+        if self.frame_times.len() == self.frame_history {
+            self.frame_times.remove(0);
+        }
+        self.frame_times.push(ms);
+    }
+
+    /// The last N frame totals in milliseconds, oldest first — ready to plot.
+    pub fn frame_times(&self) -> &[f64] {
+        &self.frame_times
+    }
+
     /// Record a sample
     pub fn record(&mut self, name: &'static str, ns: u64) {
         let sample = self.samples.entry(name).or_insert(ProfileSample {
-            total_ns: 0,
-            count: 0,
             min_ns: u64::MAX,
-            max_ns: 0,
+            ..ProfileSample::default()
         });
-        
-        sample.total_ns += ns;
-        sample.count += 1;
-        sample.min_ns = sample.min_ns.min(ns);
-        sample.max_ns = sample.max_ns.max(ns);
+        sample.observe(ns);
     }
-    
+
+    /// Open a named scope on this thread's nesting stack, recording its start
+    /// offset. Paired with [`close`](Self::close); the [`ScopedTimer`] drives
+    /// both so nested scopes show up as nested spans in the trace.
+    ///
+    /// [`ScopedTimer`]: super::timing::ScopedTimer
+    pub fn open(&mut self, name: &'static str) {
+        let start_ns = self.epoch.elapsed().as_nanos() as u64;
+        let depth = self.stack.len();
+        self.stack.push(OpenScope { name, start_ns, depth });
+    }
+
+    /// Close the innermost open scope, recording its `dur_ns` into the sample
+    /// table and appending a nested trace event.
+    pub fn close(&mut self, dur_ns: u64) {
+        let Some(scope) = self.stack.pop() else { return };
+        self.record(scope.name, dur_ns);
+        self.events.push(TraceEvent {
+            name: scope.name,
+            start_ns: scope.start_ns,
+            dur_ns,
+            thread: current_thread_id(),
+            depth: scope.depth,
+        });
+    }
+
+    /// Per-frame breakdown: every accumulated scope paired with its sample,
+    /// sorted by total time descending so the hottest scopes lead.
+    pub fn report(&self) -> Vec<ReportEntry> {
+        let mut entries: Vec<ReportEntry> = self
+            .samples
+            .iter()
+            .map(|(&name, sample)| ReportEntry { name, sample: sample.clone() })
+            .collect();
+        entries.sort_by(|a, b| b.sample.total_ns.cmp(&a.sample.total_ns));
+        entries
+    }
+
     /// Get sample by name
     pub fn get(&self, name: &str) -> Option<&ProfileSample> {
         self.samples.get(name)
@@ -58,8 +196,33 @@ impl Profiler {
     /// Reset all samples
     pub fn reset(&mut self) {
         self.samples.clear();
+        self.events.clear();
     }
-    
+
+    /// Write the recorded scopes as Chrome Tracing JSON (an array of `ph: "X"`
+    /// duration events with `ts`/`dur` in microseconds), loadable in
+    /// `chrome://tracing` or Perfetto.
+    pub fn export_chrome_trace(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        use std::io::Write;
+        let mut file = std::fs::File::create(path)?;
+        write!(file, "[")?;
+        for (i, e) in self.events.iter().enumerate() {
+            if i > 0 {
+                write!(file, ",")?;
+            }
+            write!(
+                file,
+                "{{\"name\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{}}}",
+                e.name,
+                e.start_ns / 1000,
+                e.dur_ns / 1000,
+                e.thread
+            )?;
+        }
+        write!(file, "]")?;
+        Ok(())
+    }
+
     /// Print summary
     pub fn print_summary(&self) {
         println!("\n=== Profiler Summary ===");
@@ -81,3 +244,57 @@ impl Default for Profiler {
         Self::new()
     }
 }
+
+/// RAII guard returned by [`Profiler::scope`]; records its elapsed time on drop.
+pub struct ScopeGuard<'a> {
+    profiler: &'a mut Profiler,
+    name: &'static str,
+    start_ns: u64,
+    timer: Timer,
+}
+
+impl Drop for ScopeGuard<'_> {
+    fn drop(&mut self) {
+        let dur_ns = self.timer.elapsed_ns();
+        self.profiler.record(self.name, dur_ns);
+        self.profiler.events.push(TraceEvent {
+            name: self.name,
+            start_ns: self.start_ns,
+            dur_ns,
+            thread: current_thread_id(),
+            depth: 0,
+        });
+    }
+}
+
+thread_local! {
+    /// The calling thread's profiler registry, fed by [`ScopedTimer`] and
+    /// [`time_scope!`]. Per-thread so scopes never contend on a lock.
+    ///
+    /// [`ScopedTimer`]: super::timing::ScopedTimer
+    /// [`time_scope!`]: crate::time_scope
+    static THREAD_PROFILER: RefCell<Profiler> = RefCell::new(Profiler::new());
+}
+
+/// Run `f` with mutable access to the calling thread's profiler.
+pub fn with_thread_profiler<R>(f: impl FnOnce(&mut Profiler) -> R) -> R {
+    THREAD_PROFILER.with(|p| f(&mut p.borrow_mut()))
+}
+
+/// Open a scope on the thread-local profiler; see [`Profiler::open`].
+pub fn thread_scope_open(name: &'static str) {
+    with_thread_profiler(|p| p.open(name));
+}
+
+/// Close the innermost thread-local scope; see [`Profiler::close`].
+pub fn thread_scope_close(dur_ns: u64) {
+    with_thread_profiler(|p| p.close(dur_ns));
+}
+
+/// A stable numeric id for the current thread, hashed from its `ThreadId`.
+fn current_thread_id() -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    std::thread::current().id().hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,198 @@
+//! GGRS-style networked rollback session.
+//!
+//! Where [`crate::rollback::Session`] is the transport-agnostic snapshot/replay
+//! core, `RollbackSession` is the P2P layer two players put around a shared
+//! [`Physics2DGame`](crate)-style simulation: it wraps the deterministic
+//! `fixed_update`/`GameLoop::tick` path, exchanges a small fixed-size input
+//! struct with the remote peer, and predicts the frames whose remote input has
+//! not arrived yet by repeating the last value seen.
+//!
+//! When an authoritative input for a past frame arrives and differs from the
+//! prediction, [`confirm_remote`](RollbackSession::confirm_remote) restores the
+//! snapshot at that frame, overwrites the predicted input, and re-runs
+//! `fixed_update` forward to the present. A per-frame FNV checksum lets peers
+//! detect desyncs.
+//!
+//! The whole scheme only works if the wrapped [`Simulation`] is pure in
+//! `(state, input)` — fixed `fixed_dt`, stable entity iteration order, no
+//! wall-clock and no unseeded RNG.
+
+use crate::rollback::{checksum, Session, Simulation};
+
+/// Number of players a session tracks. The combined per-frame input handed to
+/// the simulation is one [`PlayerInput`] per player, in handle order.
+pub const MAX_PLAYERS: usize = 2;
+
+/// Combined input for a single frame: one [`PlayerInput`] per player.
+pub type FrameInputs = [PlayerInput; MAX_PLAYERS];
+
+/// Button bit for a [`PlayerInput::buttons`] bitfield.
+pub mod buttons {
+    pub const LEFT: u16 = 1 << 0;
+    pub const RIGHT: u16 = 1 << 1;
+    pub const UP: u16 = 1 << 2;
+    pub const DOWN: u16 = 1 << 3;
+    pub const JUMP: u16 = 1 << 4;
+    pub const ATTACK: u16 = 1 << 5;
+}
+
+/// One player's input for one frame — a small, fixed-size, `Pod`-able struct
+/// (four bytes on the wire) exchanged with the remote peer each frame.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PlayerInput {
+    /// Bitfield of held actions, see [`buttons`].
+    pub buttons: u16,
+    /// Quantized horizontal stick, `-127..=127`.
+    pub move_x: i8,
+    /// Quantized vertical stick, `-127..=127`.
+    pub move_y: i8,
+}
+
+impl PlayerInput {
+    /// Whether `button` (a [`buttons`] bit) is held this frame.
+    #[inline]
+    pub fn held(&self, button: u16) -> bool {
+        self.buttons & button != 0
+    }
+
+    /// Serialize to the fixed four-byte little-endian wire form.
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; 4] {
+        let b = self.buttons.to_le_bytes();
+        [b[0], b[1], self.move_x as u8, self.move_y as u8]
+    }
+
+    /// Parse the four-byte wire form produced by [`to_bytes`](Self::to_bytes).
+    #[inline]
+    pub fn from_bytes(bytes: [u8; 4]) -> Self {
+        Self {
+            buttons: u16::from_le_bytes([bytes[0], bytes[1]]),
+            move_x: bytes[2] as i8,
+            move_y: bytes[3] as i8,
+        }
+    }
+}
+
+/// Result of a call to [`RollbackSession::advance`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdvanceStatus {
+    /// The frame was simulated (with real or predicted remote input).
+    Stepped,
+    /// The prediction window is full; the caller must wait for remote input
+    /// before stepping again. No frame was produced.
+    Stalled,
+}
+
+/// A two-player rollback session over a deterministic [`Simulation`] whose
+/// per-frame input is [`FrameInputs`].
+pub struct RollbackSession<S>
+where
+    S: Simulation<Input = FrameInputs>,
+{
+    session: Session<S>,
+    /// Which slot in [`FrameInputs`] this peer drives locally.
+    local_handle: usize,
+    /// Most recent input received from the remote peer, repeated while newer
+    /// remote input is missing.
+    last_remote: PlayerInput,
+    /// Highest frame for which authoritative remote input has been applied.
+    confirmed_frame: Option<u64>,
+    /// Frames we are allowed to run ahead of `confirmed_frame` on prediction.
+    max_prediction: u32,
+}
+
+impl<S> RollbackSession<S>
+where
+    S: Simulation<Input = FrameInputs>,
+{
+    /// Create a session driving `local_handle`, keeping `max_frames` snapshots
+    /// and predicting at most `max_prediction` frames ahead of confirmation.
+    pub fn new(sim: S, local_handle: usize, max_frames: usize, max_prediction: u32) -> Self {
+        Self {
+            session: Session::new(sim, max_frames),
+            local_handle: local_handle.min(MAX_PLAYERS - 1),
+            last_remote: PlayerInput::default(),
+            confirmed_frame: None,
+            max_prediction: max_prediction.max(1),
+        }
+    }
+
+    /// The frame the next [`advance`](Self::advance) will produce.
+    pub fn frame(&self) -> u64 {
+        self.session.frame()
+    }
+
+    /// Borrow the wrapped simulation, e.g. to render it.
+    pub fn simulation(&self) -> &S {
+        self.session.simulation()
+    }
+
+    /// Combine local and remote inputs into a [`FrameInputs`] in handle order.
+    fn combine(&self, local: PlayerInput, remote: PlayerInput) -> FrameInputs {
+        let mut inputs = [PlayerInput::default(); MAX_PLAYERS];
+        inputs[self.local_handle] = local;
+        inputs[1 - self.local_handle] = remote;
+        inputs
+    }
+
+    /// Advance one fixed frame. `remote` is the authoritative input for this
+    /// frame if it has already arrived; otherwise the last known remote input
+    /// is repeated as a prediction. Returns [`AdvanceStatus::Stalled`] without
+    /// stepping when the prediction window is exhausted.
+    pub fn advance(&mut self, local: PlayerInput, remote: Option<PlayerInput>) -> AdvanceStatus {
+        if remote.is_none() {
+            let confirmed = self.confirmed_frame.map_or(0, |f| f + 1);
+            if self.frame().saturating_sub(confirmed) >= self.max_prediction as u64 {
+                return AdvanceStatus::Stalled;
+            }
+        }
+
+        // A real remote input confirms this frame; a missing one is predicted
+        // by repeating the last received value.
+        if let Some(r) = remote {
+            self.last_remote = r;
+            self.confirmed_frame = Some(self.frame());
+        }
+
+        let effective = remote.unwrap_or(self.last_remote);
+        let combined = self.combine(local, effective);
+        self.session.advance(combined);
+        AdvanceStatus::Stepped
+    }
+
+    /// Apply an authoritative remote input for a past `frame`. If it differs
+    /// from the prediction recorded there, roll back and re-simulate forward to
+    /// the present with the corrected input. Returns `false` if `frame` is no
+    /// longer retained.
+    pub fn confirm_remote(&mut self, frame: u64, remote: PlayerInput) -> bool {
+        let Some(prev) = self.session.input_at(frame).copied() else {
+            return false;
+        };
+        let mut corrected = prev;
+        corrected[1 - self.local_handle] = remote;
+
+        if self.confirmed_frame.map_or(true, |f| frame > f) {
+            self.confirmed_frame = Some(frame);
+            self.last_remote = remote;
+        }
+        self.session.confirm_input(frame, corrected)
+    }
+
+    /// GGRS `save_state` hook: the checksum of the snapshot retained for
+    /// `frame`, for desync comparison with the peer.
+    pub fn save_state(&self, frame: u64) -> Option<u64> {
+        self.session.checksum_at(frame)
+    }
+
+    /// GGRS `load_state` hook: restore the simulation to the snapshot at
+    /// `frame` without replaying forward. Returns `false` if not retained.
+    pub fn load_state(&mut self, frame: u64) -> bool {
+        self.session.rollback_to(frame)
+    }
+
+    /// Checksum of the simulation's current state, for desync detection.
+    pub fn current_checksum(&self) -> u64 {
+        checksum(&self.session.simulation().snapshot())
+    }
+}
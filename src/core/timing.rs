@@ -123,23 +123,32 @@ impl Default for Timer {
     }
 }
 
-/// Scope-based timing for profiling
-pub struct ScopedTimer<'a> {
-    name: &'a str,
+/// Scope-based timing for profiling.
+///
+/// On construction the scope is pushed onto the thread-local
+/// [`Profiler`](super::profiler::Profiler) nesting stack; on drop its elapsed
+/// nanoseconds are folded into that scope's running statistics and a nested trace
+/// event is recorded. The drop also logs at `trace` level, preserving the
+/// original lightweight behavior.
+pub struct ScopedTimer {
+    name: &'static str,
     timer: Timer,
 }
 
-impl<'a> ScopedTimer<'a> {
-    pub fn new(name: &'a str) -> Self {
+impl ScopedTimer {
+    pub fn new(name: &'static str) -> Self {
         let mut timer = Timer::new();
         timer.start();
+        super::profiler::thread_scope_open(name);
         Self { name, timer }
     }
 }
 
-impl<'a> Drop for ScopedTimer<'a> {
+impl Drop for ScopedTimer {
     fn drop(&mut self) {
-        log::trace!("{}: {:.3}ms", self.name, self.timer.elapsed_ms());
+        let elapsed_ns = self.timer.elapsed_ns();
+        super::profiler::thread_scope_close(elapsed_ns);
+        log::trace!("{}: {:.3}ms", self.name, elapsed_ns as f64 / 1_000_000.0);
     }
 }
 
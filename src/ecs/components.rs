@@ -71,6 +71,10 @@ pub enum Collider {
     Circle { radius: f32 },
     AABB { half_extents: Vec2 },
     OBB { half_extents: Vec2 },
+    /// Triangular ramp filling one half of its bounding box. `rise`/`run` set
+    /// the gradient and `facing` selects which side is the high corner
+    /// (`+1` = high on the right, `-1` = high on the left).
+    Slope { rise: f32, run: f32, facing: i8 },
 }
 
 impl Default for Collider {
@@ -129,3 +133,39 @@ impl Health {
         self.current <= 0.0
     }
 }
+
+/// Damage dealt to whatever an entity strikes, e.g. a fast-moving projectile.
+/// The collision pipeline subtracts `amount` from the struck entity's
+/// [`Health`] on contact.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Damage {
+    pub amount: f32,
+}
+
+/// Minimal combat state for an ECS-driven fighter. Position comes from
+/// [`Transform`] and movement from [`Velocity`]; this only holds the bits a
+/// melee system needs to resolve a hit between two entities.
+#[derive(Clone, Copy, Debug)]
+pub struct Fighter {
+    pub health: u8,
+    pub facing_right: bool,
+    pub attacking: bool,
+}
+
+impl Default for Fighter {
+    fn default() -> Self {
+        Self {
+            health: 100,
+            facing_right: true,
+            attacking: false,
+        }
+    }
+}
+
+/// Held input state driving one [`Fighter`] entity for the current frame.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Input {
+    pub left: bool,
+    pub right: bool,
+    pub attack: bool,
+}
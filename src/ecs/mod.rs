@@ -54,6 +54,48 @@ impl World {
         self.inner.query::<Q>()
     }
     
+    /// Borrow `T` mutably from two *different* entities at once - the ECS
+    /// equivalent of `<[T]>::split_at_mut`. Without this, a system that makes
+    /// two entities interact (two fighters trading a hit, a constraint
+    /// between two bodies) has to copy one side's state out first just to get
+    /// past the borrow checker. Returns `None` if `a == b` or either entity is
+    /// missing or lacks the component.
+    pub fn get_two_mut<T: hecs::Component>(
+        &mut self,
+        a: Entity,
+        b: Entity,
+    ) -> Option<(&mut T, &mut T)> {
+        if a == b {
+            return None;
+        }
+        // SAFETY: `query_one_mut` only needs `&mut self.inner` for the
+        // duration of each call and hands back a reference into that one
+        // entity's row. Since `a != b`, the two rows it returns never
+        // overlap, so splitting the exclusive borrow via a raw pointer here
+        // is the same disjointness argument `split_at_mut` relies on for a
+        // slice - the borrow checker just can't see it on its own.
+        let world: *mut hecs::World = &mut self.inner;
+        unsafe {
+            let ref_a = (*world).query_one_mut::<&mut T>(a).ok()?;
+            let ref_b = (*world).query_one_mut::<&mut T>(b).ok()?;
+            Some((ref_a, ref_b))
+        }
+    }
+
+    /// Run `f` with mutable access to both `a` and `b`'s `T` component at
+    /// once, or return `None` without calling `f` if they alias or either is
+    /// missing the component. A convenience wrapper over [`World::get_two_mut`]
+    /// for callers that just want to mutate both sides in one closure.
+    pub fn query_disjoint<T: hecs::Component, R>(
+        &mut self,
+        a: Entity,
+        b: Entity,
+        f: impl FnOnce(&mut T, &mut T) -> R,
+    ) -> Option<R> {
+        let (ref_a, ref_b) = self.get_two_mut::<T>(a, b)?;
+        Some(f(ref_a, ref_b))
+    }
+
     /// Get inner hecs world
     pub fn inner(&self) -> &hecs::World {
         &self.inner
@@ -3,24 +3,120 @@
 use crate::ecs::World;
 use crate::ecs::components::*;
 
-/// Movement system - updates positions based on velocity
+/// Movement system - updates positions based on velocity.
+///
+/// Entities are processed in ascending id order so a given set of inputs
+/// produces bit-identical state on every machine — the iteration order must not
+/// leak archetype/storage layout into the simulation, which rollback netcode
+/// relies on.
 pub fn movement_system(world: &mut World, dt: f32) {
-    for (_, (transform, velocity)) in world.inner_mut().query_mut::<(&mut Transform, &Velocity)>() {
-        transform.position.x += velocity.linear.x * dt;
-        transform.position.y += velocity.linear.y * dt;
-        transform.rotation += velocity.angular * dt;
+    let mut entities: Vec<hecs::Entity> = world
+        .inner()
+        .query::<(&Transform, &Velocity)>()
+        .iter()
+        .map(|(e, _)| e)
+        .collect();
+    entities.sort_unstable_by_key(|e| e.id());
+
+    for entity in entities {
+        if let Ok((transform, velocity)) =
+            world.inner_mut().query_one_mut::<(&mut Transform, &Velocity)>(entity)
+        {
+            transform.position.x += velocity.linear.x * dt;
+            transform.position.y += velocity.linear.y * dt;
+            transform.rotation += velocity.angular * dt;
+        }
     }
 }
 
-/// Gravity system - applies gravity to entities with rigid bodies
+/// Gravity system - applies gravity to entities with rigid bodies. Iterates in
+/// ascending id order for the same determinism reason as [`movement_system`].
 pub fn gravity_system(world: &mut World, gravity: f32, dt: f32) {
-    for (_, (velocity, body)) in world.inner_mut().query_mut::<(&mut Velocity, &RigidBody)>() {
-        if body.inv_mass > 0.0 {
-            velocity.linear.y += gravity * dt;
+    let mut entities: Vec<hecs::Entity> = world
+        .inner()
+        .query::<(&Velocity, &RigidBody)>()
+        .iter()
+        .map(|(e, _)| e)
+        .collect();
+    entities.sort_unstable_by_key(|e| e.id());
+
+    for entity in entities {
+        if let Ok((velocity, body)) =
+            world.inner_mut().query_one_mut::<(&mut Velocity, &RigidBody)>(entity)
+        {
+            if body.inv_mass > 0.0 {
+                velocity.linear.y += gravity * dt;
+            }
+        }
+    }
+}
+
+/// Drive a [`Fighter`] entity from its held [`Input`] for one frame: walking
+/// sets horizontal velocity and facing, and the attack button latches
+/// `Fighter::attacking` so [`resolve_fighter_hit`] can see it this frame.
+pub fn fighter_input_system(world: &mut World, speed: f32) {
+    let mut entities: Vec<hecs::Entity> = world
+        .inner()
+        .query::<(&Fighter, &Input)>()
+        .iter()
+        .map(|(e, _)| e)
+        .collect();
+    entities.sort_unstable_by_key(|e| e.id());
+
+    for entity in entities {
+        if let Ok((velocity, fighter, input)) =
+            world.inner_mut().query_one_mut::<(&mut Velocity, &mut Fighter, &Input)>(entity)
+        {
+            velocity.linear.x = match (input.left, input.right) {
+                (true, false) => -speed,
+                (false, true) => speed,
+                _ => 0.0,
+            };
+            if input.left {
+                fighter.facing_right = false;
+            } else if input.right {
+                fighter.facing_right = true;
+            }
+            fighter.attacking = input.attack;
         }
     }
 }
 
+/// Resolve a melee hit between two [`Fighter`] entities: if they're within
+/// `range` on the X axis and `attacker` currently has `attacking` set, deal
+/// `damage` to `defender`. Demonstrates [`World::query_disjoint`] doing the
+/// mutual read (`attacker.attacking`) and write (`defender.health`) in one
+/// call instead of copying either fighter's state out first. Returns whether
+/// a hit landed.
+pub fn resolve_fighter_hit(
+    world: &mut World,
+    attacker: hecs::Entity,
+    defender: hecs::Entity,
+    range: f32,
+    damage: u8,
+) -> bool {
+    let (Some(ax), Some(bx)) = (
+        world.get::<Transform>(attacker).map(|t| t.position.x),
+        world.get::<Transform>(defender).map(|t| t.position.x),
+    ) else {
+        return false;
+    };
+    if (ax - bx).abs() > range {
+        return false;
+    }
+
+    world
+        .query_disjoint::<Fighter, _>(attacker, defender, |attacker, defender| {
+            if attacker.attacking {
+                defender.health = defender.health.saturating_sub(damage);
+                true
+            } else {
+                false
+            }
+        })
+        .unwrap_or(false)
+}
+
 /// Health system - removes dead entities
 pub fn health_system(world: &mut World) -> Vec<hecs::Entity> {
     let dead: Vec<_> = world.inner()
@@ -29,10 +125,91 @@ pub fn health_system(world: &mut World) -> Vec<hecs::Entity> {
         .filter(|(_, health)| health.is_dead())
         .map(|(entity, _)| entity)
         .collect();
-    
+
     for entity in &dead {
         let _ = world.despawn(*entity);
     }
-    
+
     dead
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Vec2;
+
+    fn fighter_at(world: &mut World, x: f32, attacking: bool) -> hecs::Entity {
+        world.spawn((
+            Transform::new(x, 0.0),
+            Fighter { attacking, ..Fighter::default() },
+        ))
+    }
+
+    #[test]
+    fn resolve_fighter_hit_damages_defender_in_range() {
+        let mut world = World::new();
+        let attacker = fighter_at(&mut world, 0.0, true);
+        let defender = fighter_at(&mut world, 50.0, false);
+
+        let landed = resolve_fighter_hit(&mut world, attacker, defender, 80.0, 10);
+
+        assert!(landed);
+        assert_eq!(world.get::<Fighter>(defender).unwrap().health, 90);
+    }
+
+    #[test]
+    fn resolve_fighter_hit_whiffs_out_of_range() {
+        let mut world = World::new();
+        let attacker = fighter_at(&mut world, 0.0, true);
+        let defender = fighter_at(&mut world, 500.0, false);
+
+        let landed = resolve_fighter_hit(&mut world, attacker, defender, 80.0, 10);
+
+        assert!(!landed);
+        assert_eq!(world.get::<Fighter>(defender).unwrap().health, 100);
+    }
+
+    #[test]
+    fn resolve_fighter_hit_does_nothing_when_attacker_is_not_attacking() {
+        let mut world = World::new();
+        let attacker = fighter_at(&mut world, 0.0, false);
+        let defender = fighter_at(&mut world, 50.0, false);
+
+        let landed = resolve_fighter_hit(&mut world, attacker, defender, 80.0, 10);
+
+        assert!(!landed);
+        assert_eq!(world.get::<Fighter>(defender).unwrap().health, 100);
+    }
+
+    #[test]
+    fn resolve_fighter_hit_rejects_an_entity_fighting_itself() {
+        let mut world = World::new();
+        let fighter = fighter_at(&mut world, 0.0, true);
+
+        // `a == b` must hit `get_two_mut`'s early return, not alias a `&mut
+        // Fighter` against itself.
+        let landed = resolve_fighter_hit(&mut world, fighter, fighter, 80.0, 10);
+
+        assert!(!landed);
+        assert_eq!(world.get::<Fighter>(fighter).unwrap().health, 100);
+    }
+
+    #[test]
+    fn fighter_input_system_sets_velocity_and_facing_from_held_input() {
+        let mut world = World::new();
+        let entity = world.spawn((
+            Transform::new(0.0, 0.0),
+            Velocity::default(),
+            Fighter::default(),
+            Input { left: false, right: true, attack: true },
+        ));
+
+        fighter_input_system(&mut world, 100.0);
+
+        let velocity = world.get::<Velocity>(entity).unwrap();
+        assert_eq!(velocity.linear, Vec2::new(100.0, 0.0));
+        let fighter = world.get::<Fighter>(entity).unwrap();
+        assert!(fighter.facing_right);
+        assert!(fighter.attacking);
+    }
+}
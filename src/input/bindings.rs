@@ -0,0 +1,118 @@
+//! Data-driven action/axis bindings
+//!
+//! Replaces scattered `match key` blocks with a single resolver: named actions
+//! and axes map to sets of physical inputs, so control schemes load from a
+//! RON/JSON file at startup and can be remapped at runtime without recompiling.
+//!
+//! The binding types are serde-(de)serializable behind the `serde` feature,
+//! which keeps the dependency optional for builds that hard-code their scheme.
+
+use std::collections::HashMap;
+
+use super::gamepad::{Axis, GamepadButton};
+use super::{Key, MouseButton};
+
+/// A single physical input that can satisfy an action or drive an axis side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputSource {
+    Key(Key),
+    Mouse(MouseButton),
+    Pad(GamepadButton),
+}
+
+/// An axis defined by opposing input lists plus an optional analog stick axis.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AxisBinding {
+    pub positive: Vec<InputSource>,
+    pub negative: Vec<InputSource>,
+    pub gamepad_axis: Option<Axis>,
+}
+
+/// A full control scheme: named digital actions and named analog axes.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bindings {
+    pub actions: HashMap<String, Vec<InputSource>>,
+    pub axes: HashMap<String, AxisBinding>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `name` to the given alternative inputs (any one satisfies it).
+    pub fn bind_action(&mut self, name: impl Into<String>, inputs: Vec<InputSource>) {
+        self.actions.insert(name.into(), inputs);
+    }
+
+    /// Bind an axis by name.
+    pub fn bind_axis(&mut self, name: impl Into<String>, axis: AxisBinding) {
+        self.axes.insert(name.into(), axis);
+    }
+}
+
+/// Which physical device drives one player's [`Bindings`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerSource {
+    Keyboard,
+    /// A gamepad identified by its SDL GUID (see
+    /// [`crate::input::controller_db::ControllerDb`]), so the same config
+    /// keeps pointing at "player 2's pad" across reconnects in a different
+    /// USB port. `None` falls back to whichever pad connects first.
+    Gamepad { guid: Option<String> },
+}
+
+impl Default for PlayerSource {
+    fn default() -> Self {
+        PlayerSource::Keyboard
+    }
+}
+
+/// One player's complete input configuration: which device, and how its
+/// buttons/axes map to named actions.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerBindingsConfig {
+    pub source: PlayerSource,
+    pub bindings: Bindings,
+}
+
+/// A whole match's bindings, loaded from a single TOML file so two players
+/// can each take a keyboard half or a specific gamepad without recompiling.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchBindingsConfig {
+    pub player1: PlayerBindingsConfig,
+    pub player2: PlayerBindingsConfig,
+}
+
+/// Errors surfaced while loading a [`MatchBindingsConfig`].
+#[derive(Debug)]
+pub enum BindingsConfigError {
+    Io(std::io::Error),
+    Parse(String),
+}
+
+impl From<std::io::Error> for BindingsConfigError {
+    fn from(e: std::io::Error) -> Self {
+        BindingsConfigError::Io(e)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl MatchBindingsConfig {
+    /// Parse a bindings config from a TOML string.
+    pub fn from_toml(source: &str) -> Result<Self, BindingsConfigError> {
+        toml::from_str(source).map_err(|e| BindingsConfigError::Parse(e.to_string()))
+    }
+
+    /// Read and parse a bindings config from a TOML file.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, BindingsConfigError> {
+        let source = std::fs::read_to_string(path)?;
+        Self::from_toml(&source)
+    }
+}
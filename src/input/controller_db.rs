@@ -0,0 +1,190 @@
+//! SDL `gamecontrollerdb.txt` loader.
+//!
+//! The community SDL_GameControllerDB maps a controller's GUID to its logical
+//! button/axis layout, since raw HID reports disagree by vendor and OS. This
+//! parses that same text format (one entry per line, comma-separated
+//! `key:value` fields) into a [`ControllerDb`] keyed by GUID, so an arbitrary
+//! pad can be normalized to our [`GamepadButton`]/[`Axis`] enums instead of
+//! guessing button indices per device.
+//!
+//! Only the fields this engine cares about (face buttons, shoulders,
+//! start/select, d-pad, and the two sticks/triggers) are parsed; unknown SDL
+//! field names are ignored rather than rejected, since a db entry also
+//! carries platform filters and fields for inputs we don't model.
+
+use std::collections::HashMap;
+
+use super::gamepad::{Axis, GamepadButton};
+
+/// Errors surfaced while loading a controller database.
+#[derive(Debug)]
+pub enum ControllerDbError {
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for ControllerDbError {
+    fn from(e: std::io::Error) -> Self {
+        ControllerDbError::Io(e)
+    }
+}
+
+/// One controller's SDL field -> logical input mapping.
+#[derive(Clone, Debug, Default)]
+pub struct ControllerMapping {
+    pub name: String,
+    pub buttons: HashMap<String, GamepadButton>,
+    pub axes: HashMap<String, Axis>,
+}
+
+impl ControllerMapping {
+    /// Normalize an SDL button field name (`"a"`, `"leftshoulder"`, `"dpup"`, ...)
+    /// to our [`GamepadButton`], if this controller maps it to one.
+    pub fn button(&self, sdl_name: &str) -> Option<GamepadButton> {
+        self.buttons.get(sdl_name).copied()
+    }
+
+    /// Normalize an SDL axis field name (`"leftx"`, `"righttrigger"`, ...) to
+    /// our [`Axis`], if this controller maps it to one.
+    pub fn axis(&self, sdl_name: &str) -> Option<Axis> {
+        self.axes.get(sdl_name).copied()
+    }
+}
+
+/// A parsed `gamecontrollerdb.txt`, keyed by lowercase GUID.
+#[derive(Clone, Debug, Default)]
+pub struct ControllerDb {
+    mappings: HashMap<String, ControllerMapping>,
+}
+
+impl ControllerDb {
+    /// Read and parse a controller database file.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, ControllerDbError> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(Self::parse(&text))
+    }
+
+    /// Parse a controller database from its in-memory text form. Blank lines
+    /// and lines starting with `#` (comments, and the db's version header)
+    /// are ignored; malformed entries are skipped rather than failing the
+    /// whole load, since the upstream db is large and only a few entries
+    /// matter to any one machine.
+    pub fn parse(text: &str) -> Self {
+        let mut mappings = HashMap::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((guid, mapping)) = parse_entry(line) {
+                mappings.insert(guid, mapping);
+            }
+        }
+        Self { mappings }
+    }
+
+    /// Look up the mapping for a GUID (case-insensitive).
+    pub fn mapping_for(&self, guid: &str) -> Option<&ControllerMapping> {
+        self.mappings.get(&guid.to_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+}
+
+/// Parse one `guid,name,field:value,field:value,...` line.
+fn parse_entry(line: &str) -> Option<(String, ControllerMapping)> {
+    let mut fields = line.split(',');
+    let guid = fields.next()?.trim().to_lowercase();
+    let name = fields.next()?.trim().to_string();
+    if guid.is_empty() {
+        return None;
+    }
+
+    let mut mapping = ControllerMapping {
+        name,
+        ..Default::default()
+    };
+    for field in fields {
+        let field = field.trim();
+        let Some((key, value)) = field.split_once(':') else {
+            continue;
+        };
+        // `value` is an SDL input spec like `b3`, `a2`, `h0.1`; we only need
+        // the field *name* to know which logical button/axis it is, since
+        // the value addresses a platform-specific raw code we don't read
+        // directly (gilrs already normalizes those for us).
+        let _ = value;
+        if let Some(button) = sdl_button(key) {
+            mapping.buttons.insert(key.to_string(), button);
+        } else if let Some(axis) = sdl_axis(key) {
+            mapping.axes.insert(key.to_string(), axis);
+        }
+    }
+    Some((guid, mapping))
+}
+
+fn sdl_button(name: &str) -> Option<GamepadButton> {
+    Some(match name {
+        "a" => GamepadButton::South,
+        "b" => GamepadButton::East,
+        "x" => GamepadButton::West,
+        "y" => GamepadButton::North,
+        "leftshoulder" => GamepadButton::LeftShoulder,
+        "rightshoulder" => GamepadButton::RightShoulder,
+        "start" => GamepadButton::Start,
+        "back" | "guide" => GamepadButton::Select,
+        "dpup" => GamepadButton::DPadUp,
+        "dpdown" => GamepadButton::DPadDown,
+        "dpleft" => GamepadButton::DPadLeft,
+        "dpright" => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+fn sdl_axis(name: &str) -> Option<Axis> {
+    Some(match name {
+        "leftx" => Axis::LeftX,
+        "lefty" => Axis::LeftY,
+        "rightx" => Axis::RightX,
+        "righty" => Axis::RightY,
+        "lefttrigger" => Axis::LeftTrigger,
+        "righttrigger" => Axis::RightTrigger,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_minimal_entry() {
+        let text = "\
+# comment line, ignored
+030000005e040000ff02000000000000,Xbox One Controller,a:b0,b:b1,x:b2,y:b3,\
+leftshoulder:b4,rightshoulder:b5,start:b6,back:b7,leftx:a0,lefty:a1,\
+righttrigger:a2,platform:Linux,\n";
+        let db = ControllerDb::parse(text);
+        assert_eq!(db.len(), 1);
+
+        let mapping = db
+            .mapping_for("030000005e040000ff02000000000000")
+            .expect("entry should parse");
+        assert_eq!(mapping.name, "Xbox One Controller");
+        assert_eq!(mapping.button("a"), Some(GamepadButton::South));
+        assert_eq!(mapping.button("leftshoulder"), Some(GamepadButton::LeftShoulder));
+        assert_eq!(mapping.axis("leftx"), Some(Axis::LeftX));
+        assert_eq!(mapping.button("platform"), None);
+    }
+
+    #[test]
+    fn blank_and_comment_lines_are_skipped() {
+        let db = ControllerDb::parse("\n# just a comment\n\n");
+        assert!(db.is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+//! Gamepad input layer
+//!
+//! Models attached controllers as first-class input alongside keys and mouse.
+//! A [`GamepadId`] is an opaque handle; [`Gamepads::enumerate`] lists the
+//! currently attached pads and [`Gamepads::is_connected`] queries one. Each pad
+//! carries a button set plus analog axes for the two sticks and two triggers.
+//!
+//! [`Gamepads::axis`] applies a configurable *radial* deadzone: stick
+//! magnitudes below the threshold read as zero, and the remainder is rescaled
+//! to the full range so motion starts smoothly at the deadzone edge.
+
+use std::collections::HashSet;
+
+/// Opaque handle to an attached gamepad.
+pub type GamepadId = usize;
+
+/// Default radial deadzone applied to the analog sticks.
+pub const DEFAULT_DEADZONE: f32 = 0.15;
+
+/// Analog axes exposed by a gamepad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// Gamepad face/shoulder buttons (subset).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftShoulder,
+    RightShoulder,
+    Start,
+    Select,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// State of a single gamepad.
+#[derive(Clone, Debug, Default)]
+pub struct Gamepad {
+    pub connected: bool,
+    /// SDL GUID reported by the backend (e.g. from `gilrs`), used to match a
+    /// pad against a [`crate::input::bindings::PlayerSource::Gamepad`] entry
+    /// in a loaded bindings config and to look up its mapping in a
+    /// [`crate::input::controller_db::ControllerDb`].
+    pub guid: Option<String>,
+    buttons: HashSet<GamepadButton>,
+    /// Raw axis values, indexed by [`Axis`].
+    left: (f32, f32),
+    right: (f32, f32),
+    triggers: (f32, f32),
+}
+
+impl Gamepad {
+    pub fn is_button_down(&self, button: GamepadButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    /// Push a button edge from a backend (`gilrs`, or a test harness).
+    pub fn set_button(&mut self, button: GamepadButton, down: bool) {
+        if down {
+            self.buttons.insert(button);
+        } else {
+            self.buttons.remove(&button);
+        }
+    }
+
+    /// Push a raw axis value from a backend; deadzoning happens later in
+    /// [`Gamepads::axis`].
+    pub fn set_axis(&mut self, axis: Axis, value: f32) {
+        let value = value.clamp(-1.0, 1.0);
+        match axis {
+            Axis::LeftX => self.left.0 = value,
+            Axis::LeftY => self.left.1 = value,
+            Axis::RightX => self.right.0 = value,
+            Axis::RightY => self.right.1 = value,
+            Axis::LeftTrigger => self.triggers.0 = value,
+            Axis::RightTrigger => self.triggers.1 = value,
+        }
+    }
+
+    /// Raw (pre-deadzone) value for `axis`, clamped to `-1.0..=1.0`.
+    fn raw(&self, axis: Axis) -> f32 {
+        let v = match axis {
+            Axis::LeftX => self.left.0,
+            Axis::LeftY => self.left.1,
+            Axis::RightX => self.right.0,
+            Axis::RightY => self.right.1,
+            Axis::LeftTrigger => self.triggers.0,
+            Axis::RightTrigger => self.triggers.1,
+        };
+        v.clamp(-1.0, 1.0)
+    }
+}
+
+/// Registry of all gamepads known to the input layer.
+#[derive(Clone, Debug)]
+pub struct Gamepads {
+    pads: Vec<Gamepad>,
+    deadzone: f32,
+}
+
+impl Default for Gamepads {
+    fn default() -> Self {
+        Self { pads: Vec::new(), deadzone: DEFAULT_DEADZONE }
+    }
+}
+
+impl Gamepads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_deadzone(&mut self, deadzone: f32) {
+        self.deadzone = deadzone.clamp(0.0, 1.0);
+    }
+
+    /// Ids of all currently attached pads.
+    pub fn enumerate(&self) -> Vec<GamepadId> {
+        self.pads
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.connected)
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    pub fn is_connected(&self, id: GamepadId) -> bool {
+        self.pads.get(id).map(|p| p.connected).unwrap_or(false)
+    }
+
+    /// First connected pad whose GUID matches, for resolving a bindings
+    /// config's `PlayerSource::Gamepad { guid }` to a live `GamepadId`.
+    pub fn find_by_guid(&self, guid: &str) -> Option<GamepadId> {
+        self.pads
+            .iter()
+            .enumerate()
+            .find(|(_, p)| p.connected && p.guid.as_deref() == Some(guid))
+            .map(|(id, _)| id)
+    }
+
+    /// Mutable access to a pad, growing the registry so the backend can push
+    /// freshly connected devices by id.
+    pub fn pad_mut(&mut self, id: GamepadId) -> &mut Gamepad {
+        if id >= self.pads.len() {
+            self.pads.resize_with(id + 1, Gamepad::default);
+        }
+        &mut self.pads[id]
+    }
+
+    pub fn pad(&self, id: GamepadId) -> Option<&Gamepad> {
+        self.pads.get(id)
+    }
+
+    /// Deadzoned, rescaled value for `axis` on pad `id`, in `-1.0..=1.0`.
+    pub fn axis(&self, id: GamepadId, axis: Axis) -> f32 {
+        let Some(pad) = self.pads.get(id) else {
+            return 0.0;
+        };
+        if !pad.connected {
+            return 0.0;
+        }
+
+        match axis {
+            // Sticks use a radial deadzone over the whole stick vector.
+            Axis::LeftX | Axis::LeftY | Axis::RightX | Axis::RightY => {
+                let (xa, ya) = if matches!(axis, Axis::LeftX | Axis::LeftY) {
+                    (Axis::LeftX, Axis::LeftY)
+                } else {
+                    (Axis::RightX, Axis::RightY)
+                };
+                let x = pad.raw(xa);
+                let y = pad.raw(ya);
+                let mag = (x * x + y * y).sqrt();
+                if mag < self.deadzone {
+                    return 0.0;
+                }
+                // Rescale the remainder past the deadzone to the full range.
+                let scaled = ((mag - self.deadzone) / (1.0 - self.deadzone)).min(1.0);
+                let component = pad.raw(axis);
+                if mag > 0.0 {
+                    (component / mag) * scaled
+                } else {
+                    0.0
+                }
+            }
+            // Triggers are one-dimensional: a simple threshold.
+            Axis::LeftTrigger | Axis::RightTrigger => {
+                let v = pad.raw(axis);
+                if v.abs() < self.deadzone {
+                    0.0
+                } else {
+                    v
+                }
+            }
+        }
+    }
+}
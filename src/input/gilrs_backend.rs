@@ -0,0 +1,96 @@
+//! Pushes `gilrs` controller events into the engine's [`Gamepads`] registry.
+//!
+//! Gated behind the `gilrs` feature so headless builds, and platforms without
+//! a controller backend, don't pull in the dependency. `gilrs` already
+//! normalizes raw HID reports to its own `Button`/`Axis` enums per OS; this
+//! just re-maps those onto ours and records each pad's GUID so a loaded
+//! [`crate::input::bindings::PlayerSource::Gamepad`] entry can find it again
+//! across runs.
+
+use super::gamepad::{Axis, GamepadButton, Gamepads};
+
+/// Wraps a live `gilrs::Gilrs` instance and drains its event queue into a
+/// [`Gamepads`] registry once per frame.
+pub struct GilrsBackend {
+    gilrs: gilrs::Gilrs,
+}
+
+impl GilrsBackend {
+    pub fn new() -> Result<Self, gilrs::Error> {
+        Ok(Self {
+            gilrs: gilrs::Gilrs::new()?,
+        })
+    }
+
+    /// Drain pending events since the last call, applying them to `gamepads`.
+    /// Call once per frame before reading any gamepad state that frame.
+    pub fn poll(&mut self, gamepads: &mut Gamepads) {
+        while let Some(event) = self.gilrs.next_event() {
+            let id: usize = usize::from(event.id);
+            let pad = gamepads.pad_mut(id);
+            match event.event {
+                gilrs::EventType::Connected => {
+                    pad.connected = true;
+                    let info = self.gilrs.gamepad(event.id);
+                    pad.guid = Some(guid_string(&info));
+                }
+                gilrs::EventType::Disconnected => pad.connected = false,
+                gilrs::EventType::ButtonPressed(button, _) => {
+                    if let Some(b) = map_button(button) {
+                        pad.set_button(b, true);
+                    }
+                }
+                gilrs::EventType::ButtonReleased(button, _) => {
+                    if let Some(b) = map_button(button) {
+                        pad.set_button(b, false);
+                    }
+                }
+                gilrs::EventType::AxisChanged(axis, value, _) => {
+                    if let Some(a) = map_axis(axis) {
+                        pad.set_axis(a, value);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// `gilrs` exposes the GUID as raw bytes; the SDL controller db (and our
+/// [`crate::input::controller_db::ControllerDb`]) key entries by its hex
+/// string form, so that's what we store on the pad too.
+fn guid_string(gamepad: &gilrs::Gamepad) -> String {
+    gamepad.uuid().iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn map_button(button: gilrs::Button) -> Option<GamepadButton> {
+    use gilrs::Button;
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::West => GamepadButton::West,
+        Button::North => GamepadButton::North,
+        Button::LeftTrigger => GamepadButton::LeftShoulder,
+        Button::RightTrigger => GamepadButton::RightShoulder,
+        Button::Start => GamepadButton::Start,
+        Button::Select => GamepadButton::Select,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+fn map_axis(axis: gilrs::Axis) -> Option<Axis> {
+    use gilrs::Axis as GilrsAxis;
+    Some(match axis {
+        GilrsAxis::LeftStickX => Axis::LeftX,
+        GilrsAxis::LeftStickY => Axis::LeftY,
+        GilrsAxis::RightStickX => Axis::RightX,
+        GilrsAxis::RightStickY => Axis::RightY,
+        GilrsAxis::LeftZ => Axis::LeftTrigger,
+        GilrsAxis::RightZ => Axis::RightTrigger,
+        _ => return None,
+    })
+}
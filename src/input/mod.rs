@@ -1,24 +1,43 @@
 //! Input handling module
-//! 
+//!
 //! Rust handles all input logic. No ASM needed here.
 
+pub mod gamepad;
+pub mod bindings;
+pub mod controller_db;
+pub mod source;
+#[cfg(feature = "gilrs")]
+pub mod gilrs_backend;
+
+pub use gamepad::{Axis, Gamepad, GamepadButton, GamepadId, Gamepads};
+pub use bindings::{
+    AxisBinding, Bindings, BindingsConfigError, InputSource, MatchBindingsConfig,
+    PlayerBindingsConfig, PlayerSource,
+};
+pub use controller_db::{ControllerDb, ControllerDbError, ControllerMapping};
+pub use source::{FighterButtons, GamepadInput, KeyboardInput, PlayerInput};
+#[cfg(feature = "gilrs")]
+pub use gilrs_backend::GilrsBackend;
+
 use std::collections::HashSet;
 
 /// Keyboard key codes (subset)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u32)]
 pub enum Key {
     W, A, S, D,
     Up, Down, Left, Right,
     Space, Enter, Escape,
     Shift, Ctrl, Alt,
-    Q, E, R, F,
+    Q, E, R, F, G, K, L,
     Num1, Num2, Num3, Num4, Num5,
     Unknown,
 }
 
 /// Mouse button
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseButton {
     Left,
     Right,
@@ -35,6 +54,8 @@ pub struct InputState {
     mouse_position: (f32, f32),
     mouse_delta: (f32, f32),
     scroll_delta: f32,
+    gamepads: Gamepads,
+    bindings: Bindings,
 }
 
 impl InputState {
@@ -122,7 +143,92 @@ impl InputState {
     pub fn scroll_delta(&self) -> f32 {
         self.scroll_delta
     }
-    
+
+    /// Shared view of the attached gamepads
+    pub fn gamepads(&self) -> &Gamepads {
+        &self.gamepads
+    }
+
+    /// Mutable view for the platform backend to push device state
+    pub fn gamepads_mut(&mut self) -> &mut Gamepads {
+        &mut self.gamepads
+    }
+
+    /// Deadzoned analog value for `axis` on gamepad `id`, in `-1.0..=1.0`
+    pub fn gamepad_axis(&self, id: GamepadId, axis: Axis) -> f32 {
+        self.gamepads.axis(id, axis)
+    }
+
+    /// Replace the active control scheme (e.g. loaded from a config file).
+    pub fn set_bindings(&mut self, bindings: Bindings) {
+        self.bindings = bindings;
+    }
+
+    /// The active control scheme.
+    pub fn bindings(&self) -> &Bindings {
+        &self.bindings
+    }
+
+    /// Is any input bound to `name` currently held down?
+    pub fn action_down(&self, name: &str) -> bool {
+        match self.bindings.actions.get(name) {
+            Some(sources) => sources.iter().any(|s| self.source_down(s)),
+            None => false,
+        }
+    }
+
+    /// Was any input bound to `name` pressed this frame?
+    pub fn action_pressed(&self, name: &str) -> bool {
+        match self.bindings.actions.get(name) {
+            Some(sources) => sources.iter().any(|s| self.source_pressed(s)),
+            None => false,
+        }
+    }
+
+    /// Resolve the axis bound to `name` into a value in `-1.0..=1.0`.
+    pub fn axis(&self, name: &str) -> f32 {
+        let Some(binding) = self.bindings.axes.get(name) else {
+            return 0.0;
+        };
+        let mut value = 0.0;
+        if binding.positive.iter().any(|s| self.source_down(s)) {
+            value += 1.0;
+        }
+        if binding.negative.iter().any(|s| self.source_down(s)) {
+            value -= 1.0;
+        }
+        if let Some(axis) = binding.gamepad_axis {
+            if let Some(&id) = self.gamepads.enumerate().first() {
+                value += self.gamepads.axis(id, axis);
+            }
+        }
+        value.clamp(-1.0, 1.0)
+    }
+
+    /// Is the physical input currently held down?
+    fn source_down(&self, source: &InputSource) -> bool {
+        match source {
+            InputSource::Key(k) => self.is_key_down(*k),
+            InputSource::Mouse(b) => self.is_mouse_down(*b),
+            InputSource::Pad(btn) => self
+                .gamepads
+                .enumerate()
+                .first()
+                .and_then(|&id| self.gamepads.pad(id))
+                .map(|pad| pad.is_button_down(*btn))
+                .unwrap_or(false),
+        }
+    }
+
+    /// Was the physical input pressed this frame? (Keys track edges; other
+    /// sources fall back to the held state.)
+    fn source_pressed(&self, source: &InputSource) -> bool {
+        match source {
+            InputSource::Key(k) => self.is_key_pressed(*k),
+            other => self.source_down(other),
+        }
+    }
+
     /// Get horizontal axis (-1, 0, 1) from WASD/Arrows
     pub fn horizontal_axis(&self) -> f32 {
         let mut axis = 0.0;
@@ -135,7 +241,9 @@ impl InputState {
         axis
     }
     
-    /// Get vertical axis (-1, 0, 1) from WASD/Arrows
+    /// Get vertical axis (-1, 0, 1) from WASD/Arrows, plus the left stick Y of
+    /// the first connected gamepad (stick forward reads as negative, matching
+    /// the keyboard convention).
     pub fn vertical_axis(&self) -> f32 {
         let mut axis = 0.0;
         if self.is_key_down(Key::W) || self.is_key_down(Key::Up) {
@@ -144,6 +252,9 @@ impl InputState {
         if self.is_key_down(Key::S) || self.is_key_down(Key::Down) {
             axis += 1.0;
         }
-        axis
+        if let Some(&id) = self.gamepads.enumerate().first() {
+            axis += self.gamepads.axis(id, Axis::LeftY);
+        }
+        axis.clamp(-1.0, 1.0)
     }
 }
@@ -0,0 +1,118 @@
+//! Abstracts "one player's input this frame" behind a trait so a game can be
+//! fed by a keyboard half, a specific gamepad, or (in tests/replays) a
+//! scripted sequence without branching on the source at every call site.
+
+use super::gamepad::GamepadButton;
+use super::{GamepadId, InputState, Key};
+
+/// The logical buttons a fighter-style game cares about, independent of
+/// whether they came from a keyboard or a gamepad.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FighterButtons {
+    pub left: bool,
+    pub right: bool,
+    pub up: bool,
+    pub down: bool,
+    pub jump: bool,
+    pub punch: bool,
+    pub kick: bool,
+}
+
+/// A source of one player's [`FighterButtons`] for the current frame.
+pub trait PlayerInput {
+    fn poll(&self, input: &InputState) -> FighterButtons;
+}
+
+/// Reads a fixed set of keyboard keys, so two players can each take a half of
+/// the same keyboard (WASD+FG vs. Arrows+KL).
+#[derive(Clone, Copy, Debug)]
+pub struct KeyboardInput {
+    pub left: Key,
+    pub right: Key,
+    pub up: Key,
+    pub down: Key,
+    pub jump: Key,
+    pub punch: Key,
+    pub kick: Key,
+}
+
+impl PlayerInput for KeyboardInput {
+    fn poll(&self, input: &InputState) -> FighterButtons {
+        FighterButtons {
+            left: input.is_key_down(self.left),
+            right: input.is_key_down(self.right),
+            up: input.is_key_down(self.up),
+            down: input.is_key_down(self.down),
+            jump: input.is_key_pressed(self.jump),
+            punch: input.is_key_pressed(self.punch),
+            kick: input.is_key_pressed(self.kick),
+        }
+    }
+}
+
+/// Reads a specific gamepad by id, falling back to all-released buttons if
+/// that pad isn't currently connected (e.g. unplugged mid-match).
+#[derive(Clone, Copy, Debug)]
+pub struct GamepadInput {
+    pub id: GamepadId,
+    pub punch: GamepadButton,
+    pub kick: GamepadButton,
+    pub jump: GamepadButton,
+}
+
+impl PlayerInput for GamepadInput {
+    fn poll(&self, input: &InputState) -> FighterButtons {
+        let Some(pad) = input.gamepads().pad(self.id).filter(|p| p.connected) else {
+            return FighterButtons::default();
+        };
+        let stick_x = input.gamepad_axis(self.id, super::Axis::LeftX);
+        FighterButtons {
+            left: stick_x < -0.5 || pad.is_button_down(GamepadButton::DPadLeft),
+            right: stick_x > 0.5 || pad.is_button_down(GamepadButton::DPadRight),
+            up: pad.is_button_down(GamepadButton::DPadUp),
+            down: pad.is_button_down(GamepadButton::DPadDown),
+            jump: pad.is_button_down(self.jump),
+            punch: pad.is_button_down(self.punch),
+            kick: pad.is_button_down(self.kick),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyboard_input_reads_the_bound_half_of_the_keyboard() {
+        let bindings = KeyboardInput {
+            left: Key::A,
+            right: Key::D,
+            up: Key::W,
+            down: Key::S,
+            jump: Key::W,
+            punch: Key::F,
+            kick: Key::G,
+        };
+        let mut input = InputState::new();
+        input.key_pressed(Key::D);
+        input.key_pressed(Key::F);
+
+        let buttons = bindings.poll(&input);
+        assert!(buttons.right);
+        assert!(buttons.punch);
+        assert!(!buttons.left);
+        assert!(!buttons.kick);
+    }
+
+    #[test]
+    fn gamepad_input_is_all_released_when_disconnected() {
+        let bindings = GamepadInput {
+            id: 0,
+            punch: GamepadButton::South,
+            kick: GamepadButton::East,
+            jump: GamepadButton::North,
+        };
+        let input = InputState::new();
+        assert_eq!(bindings.poll(&input), FighterButtons::default());
+    }
+}
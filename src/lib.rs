@@ -17,6 +17,8 @@
 //! - `render` - Software renderer, raycaster
 //! - `input` - Input handling
 //! - `audio` - Audio system
+//! - `ai` - Neural-network steering agents and genetic trainer
+//! - `content` - TOML-driven level/entity definitions
 
 pub mod core;
 pub mod ecs;
@@ -25,6 +27,11 @@ pub mod physics;
 pub mod render;
 pub mod input;
 pub mod audio;
+pub mod ai;
+pub mod content;
+pub mod rollback;
+#[cfg(feature = "scripting")]
+pub mod scripting;
 
 pub use core::*;
 pub use ecs::World;
@@ -32,6 +39,7 @@ pub use math::{Vec2, FixedPoint};
 pub use physics::PhysicsWorld;
 pub use render::Renderer;
 pub use input::InputState;
+pub use content::LevelDef;
 
 /// Engine configuration
 #[derive(Clone, Debug)]
@@ -42,6 +50,11 @@ pub struct EngineConfig {
     pub fixed_timestep: f64,
     pub max_frame_skip: u32,
     pub vsync: bool,
+    /// Seed for the engine's deterministic RNG (see [`math::XorShift`]). Games
+    /// that need reproducible "randomness" — spawn layout, wandering steering,
+    /// replay-stable jitter — derive their generators from this so the same
+    /// config always reproduces the same run.
+    pub seed: u32,
 }
 
 impl Default for EngineConfig {
@@ -53,6 +66,7 @@ impl Default for EngineConfig {
             fixed_timestep: 1.0 / 60.0,
             max_frame_skip: 5,
             vsync: true,
+            seed: 0xC0FF_EE,
         }
     }
 }
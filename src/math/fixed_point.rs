@@ -14,9 +14,24 @@ impl FixedPoint {
     pub const ONE: FixedPoint = FixedPoint(1 << 16);
     pub const HALF: FixedPoint = FixedPoint(1 << 15);
     pub const NEG_ONE: FixedPoint = FixedPoint(-(1 << 16));
-    
+
+    /// π, π/2 and 2π in 16.16, for angle reduction in the trig routines.
+    pub const PI: FixedPoint = FixedPoint(205887);
+    pub const HALF_PI: FixedPoint = FixedPoint(102944);
+    pub const TWO_PI: FixedPoint = FixedPoint(411775);
+
     const FRAC_BITS: i32 = 16;
     const SCALE: i32 = 1 << 16;
+
+    /// Number of CORDIC iterations; 16 gives ~16.16 of precision.
+    const CORDIC_ITERS: usize = 16;
+    /// CORDIC gain `K ≈ 0.607253` in 16.16, the start vector's x component so
+    /// the rotated vector lands unit-length.
+    const CORDIC_GAIN: i32 = 39797;
+    /// Precomputed `atan(2^-i)` angles in 16.16, the per-iteration rotation.
+    const CORDIC_ATAN: [i32; Self::CORDIC_ITERS] = [
+        51472, 30386, 16055, 8149, 4091, 2048, 1024, 512, 256, 128, 64, 32, 16, 8, 4, 2,
+    ];
     
     #[inline]
     pub const fn from_raw(raw: i32) -> Self {
@@ -105,6 +120,129 @@ impl FixedPoint {
     pub fn lerp(self, other: Self, t: Self) -> Self {
         self + (other - self).mul_full(t)
     }
+
+    /// Integer square root (Newton/Heron iteration on the 32.32 intermediate)
+    ///
+    /// Deterministic across targets: operates purely on integers, no FPU.
+    /// Returns `ZERO` for negative inputs.
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        // Work in 32.32 so the result lands back in 16.16 after the root.
+        let n = (self.0 as i64) << Self::FRAC_BITS;
+        let mut x = n;
+        let mut last;
+        // Heron's method: starting from `n`, the estimate roughly halves each
+        // step until it nears the root, then converges quadratically.
+        loop {
+            last = x;
+            x = (x + n / x) >> 1;
+            if x >= last {
+                break;
+            }
+        }
+        Self(last as i32)
+    }
+
+    /// Cosine and sine of `self` (radians) via CORDIC rotation mode.
+    ///
+    /// The angle is reduced into `[-π/2, π/2]` (tracking the quadrant sign),
+    /// then a unit vector starting at `(K, 0)` is rotated by `±atan(2^-i)` using
+    /// only shifts and adds, leaving cosine in `x` and sine in `y`. Integer-only,
+    /// so the result is bit-identical on every target.
+    pub fn sin_cos(self) -> (FixedPoint, FixedPoint) {
+        // Reduce into [-π, π].
+        let mut z = self.0 % Self::TWO_PI.0;
+        if z > Self::PI.0 {
+            z -= Self::TWO_PI.0;
+        } else if z < -Self::PI.0 {
+            z += Self::TWO_PI.0;
+        }
+        // Fold into [-π/2, π/2]; crossing into the far half flips both outputs.
+        let mut negate = false;
+        if z > Self::HALF_PI.0 {
+            z -= Self::PI.0;
+            negate = true;
+        } else if z < -Self::HALF_PI.0 {
+            z += Self::PI.0;
+            negate = true;
+        }
+
+        let mut x = Self::CORDIC_GAIN;
+        let mut y = 0i32;
+        for i in 0..Self::CORDIC_ITERS {
+            let dx = x >> i;
+            let dy = y >> i;
+            if z >= 0 {
+                x -= dy;
+                y += dx;
+                z -= Self::CORDIC_ATAN[i];
+            } else {
+                x += dy;
+                y -= dx;
+                z += Self::CORDIC_ATAN[i];
+            }
+        }
+
+        if negate {
+            (Self(-x), Self(-y))
+        } else {
+            (Self(x), Self(y))
+        }
+    }
+
+    /// Sine of `self` (radians); see [`sin_cos`](Self::sin_cos).
+    #[inline]
+    pub fn sin(self) -> Self {
+        self.sin_cos().1
+    }
+
+    /// Cosine of `self` (radians); see [`sin_cos`](Self::sin_cos).
+    #[inline]
+    pub fn cos(self) -> Self {
+        self.sin_cos().0
+    }
+
+    /// Four-quadrant arctangent of `y/x` via CORDIC vectoring mode, returning an
+    /// angle in `(-π, π]`. Integer-only and deterministic.
+    pub fn atan2(y: Self, x: Self) -> Self {
+        if x.0 == 0 {
+            return if y.0 > 0 {
+                Self::HALF_PI
+            } else if y.0 < 0 {
+                -Self::HALF_PI
+            } else {
+                Self::ZERO
+            };
+        }
+
+        // Vectoring converges only for positive x; reflect the left half-plane
+        // and add ±π afterwards.
+        let (mut xi, mut yi, base) = if x.0 < 0 {
+            let base = if y.0 >= 0 { Self::PI.0 } else { -Self::PI.0 };
+            (-x.0, -y.0, base)
+        } else {
+            (x.0, y.0, 0)
+        };
+
+        let mut z = 0i32;
+        for i in 0..Self::CORDIC_ITERS {
+            let dx = xi >> i;
+            let dy = yi >> i;
+            if yi > 0 {
+                xi += dy;
+                yi -= dx;
+                z += Self::CORDIC_ATAN[i];
+            } else {
+                xi -= dy;
+                yi += dx;
+                z -= Self::CORDIC_ATAN[i];
+            }
+        }
+
+        Self(base + z)
+    }
 }
 
 impl Add for FixedPoint {
@@ -172,4 +310,32 @@ mod tests {
         assert!((a - b).to_f32() - 1.0 < 0.001);
         assert!((a * b).to_f32() - 3.75 < 0.001);
     }
+
+    #[test]
+    fn test_sin_cos_matches_f32() {
+        let mut angle = -6.0;
+        while angle <= 6.0 {
+            let fx = FixedPoint::from_f32(angle);
+            assert!((fx.sin().to_f32() - angle.sin()).abs() < 0.01, "sin({angle})");
+            assert!((fx.cos().to_f32() - angle.cos()).abs() < 0.01, "cos({angle})");
+            angle += 0.1;
+        }
+    }
+
+    #[test]
+    fn test_atan2_matches_f32() {
+        let samples = [
+            (1.0, 1.0),
+            (-1.0, 1.0),
+            (-1.0, -1.0),
+            (1.0, -1.0),
+            (0.0, 1.0),
+            (0.0, -1.0),
+            (2.5, -0.5),
+        ];
+        for (y, x) in samples {
+            let fx = FixedPoint::atan2(FixedPoint::from_f32(y), FixedPoint::from_f32(x));
+            assert!((fx.to_f32() - (y as f32).atan2(x)).abs() < 0.01, "atan2({y},{x})");
+        }
+    }
 }
@@ -0,0 +1,132 @@
+//! Deterministic fixed-point 2D vector
+//!
+//! The crate's docs promise "deterministic simulation," but [`Vec2`] is `f32`
+//! and therefore diverges across CPUs/compilers. `FixedVec2` mirrors the
+//! [`Vec2`] API on top of the 16.16 [`FixedPoint`] type so positions and
+//! velocities evolve bit-identically on any target.
+//!
+//! Products use the wider 64-bit intermediate inside [`FixedPoint::mul_full`]
+//! and are then truncated back to 16.16: a product outside the 16.16 range
+//! wraps rather than saturating. Wrapping integer arithmetic is identical on
+//! every target, so `pos += vel * dt` stays deterministic even on overflow.
+
+use super::{FixedPoint, Vec2};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// 2D vector with 16.16 fixed-point components
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct FixedVec2 {
+    pub x: FixedPoint,
+    pub y: FixedPoint,
+}
+
+impl FixedVec2 {
+    pub const ZERO: FixedVec2 = FixedVec2 { x: FixedPoint::ZERO, y: FixedPoint::ZERO };
+
+    #[inline]
+    pub const fn new(x: FixedPoint, y: FixedPoint) -> Self {
+        Self { x, y }
+    }
+
+    #[inline]
+    pub fn from_ints(x: i32, y: i32) -> Self {
+        Self { x: FixedPoint::from_int(x), y: FixedPoint::from_int(y) }
+    }
+
+    /// Lossy conversion from the floating-point [`Vec2`]
+    #[inline]
+    pub fn from_vec2(v: Vec2) -> Self {
+        Self { x: FixedPoint::from_f32(v.x), y: FixedPoint::from_f32(v.y) }
+    }
+
+    /// Lossy conversion back to [`Vec2`] for rendering
+    #[inline]
+    pub fn to_vec2(self) -> Vec2 {
+        Vec2::new(self.x.to_f32(), self.y.to_f32())
+    }
+
+    #[inline]
+    pub fn dot(self, other: Self) -> FixedPoint {
+        self.x.mul_full(other.x) + self.y.mul_full(other.y)
+    }
+
+    #[inline]
+    pub fn length_squared(self) -> FixedPoint {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn length(self) -> FixedPoint {
+        self.length_squared().sqrt()
+    }
+
+    #[inline]
+    pub fn lerp(self, other: Self, t: FixedPoint) -> Self {
+        Self {
+            x: self.x.lerp(other.x, t),
+            y: self.y.lerp(other.y, t),
+        }
+    }
+
+    /// Rotate by the rotation matrix `(cos, sin)`.
+    ///
+    /// Callers pass pre-computed fixed-point `cos`/`sin` (see
+    /// [`FixedPoint::cos`]/[`FixedPoint::sin`]) so rotation stays free of the
+    /// FPU and deterministic.
+    #[inline]
+    pub fn rotate(self, cos: FixedPoint, sin: FixedPoint) -> Self {
+        Self {
+            x: self.x.mul_full(cos) - self.y.mul_full(sin),
+            y: self.x.mul_full(sin) + self.y.mul_full(cos),
+        }
+    }
+}
+
+impl Add for FixedVec2 {
+    type Output = Self;
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl Sub for FixedVec2 {
+    type Output = Self;
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl Mul<FixedPoint> for FixedVec2 {
+    type Output = Self;
+    #[inline]
+    fn mul(self, scalar: FixedPoint) -> Self {
+        Self { x: self.x.mul_full(scalar), y: self.y.mul_full(scalar) }
+    }
+}
+
+impl Neg for FixedVec2 {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self { x: -self.x, y: -self.y }
+    }
+}
+
+impl AddAssign for FixedVec2 {
+    #[inline]
+    fn add_assign(&mut self, other: Self) {
+        self.x = self.x + other.x;
+        self.y = self.y + other.y;
+    }
+}
+
+impl SubAssign for FixedVec2 {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) {
+        self.x = self.x - other.x;
+        self.y = self.y - other.y;
+    }
+}
@@ -5,7 +5,11 @@
 
 pub mod vec2;
 pub mod fixed_point;
+pub mod fixed_vec2;
 pub mod simd;
+pub mod rng;
 
 pub use vec2::Vec2;
 pub use fixed_point::FixedPoint;
+pub use fixed_vec2::FixedVec2;
+pub use rng::XorShift;
@@ -0,0 +1,120 @@
+//! Seeded pseudo-random numbers.
+//!
+//! A `std`-free 32-bit xorshift generator. Because the whole engine advertises
+//! bit-exact determinism (fixed-point math, lockstep simulation), randomness
+//! must come from an explicit seed rather than `std` entropy so that replays
+//! and rollback re-simulation reproduce the exact same "random" outcomes.
+
+use core::ops::Range;
+
+use super::FixedPoint;
+
+/// Marsaglia 32-bit xorshift generator. Cheap, deterministic, and trivially
+/// snapshot-able: the whole state is a single `u32`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct XorShift {
+    state: u32,
+}
+
+impl XorShift {
+    /// Seed the generator. The xorshift sequence collapses to all-zeroes if the
+    /// state is ever zero, so a zero seed is remapped to a fixed non-zero
+    /// constant.
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { 0x2545_F491 } else { seed },
+        }
+    }
+
+    /// Restore a generator from a previously captured [`XorShift::state`],
+    /// e.g. when loading a byte-serialized snapshot.
+    pub const fn from_state(state: u32) -> Self {
+        Self { state }
+    }
+
+    /// The raw internal state, for snapshotting alongside the rest of a
+    /// deterministic simulation.
+    pub const fn state(&self) -> u32 {
+        self.state
+    }
+
+    /// Advance the state and return the next 32-bit value.
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Return a value in `lo..hi` via modulo. An empty or inverted range yields
+    /// `lo`.
+    pub fn range(&mut self, range: Range<u32>) -> u32 {
+        if range.end <= range.start {
+            return range.start;
+        }
+        range.start + self.next_u32() % (range.end - range.start)
+    }
+
+    /// Return a float in `[0, 1)`, built from the top 24 bits so every output
+    /// is exactly representable.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Return a float in `lo..hi`. An empty or inverted range yields `lo`.
+    pub fn range_f32(&mut self, lo: f32, hi: f32) -> f32 {
+        if hi <= lo {
+            return lo;
+        }
+        lo + self.next_f32() * (hi - lo)
+    }
+
+    /// Return a [`FixedPoint`] in `[0, 1)`, built straight from the low 16 raw
+    /// bits so fixed-point sims never round-trip through `f32`.
+    pub fn next_fixed(&mut self) -> FixedPoint {
+        FixedPoint::from_raw((self.next_u32() & 0xFFFF) as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = XorShift::new(0xC0FF_EE);
+        let mut b = XorShift::new(0xC0FF_EE);
+        for _ in 0..1000 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let mut a = XorShift::new(1);
+        let mut b = XorShift::new(2);
+        assert_ne!(a.next_u32(), b.next_u32());
+    }
+
+    #[test]
+    fn next_f32_stays_in_unit_range() {
+        let mut rng = XorShift::new(42);
+        for _ in 0..1000 {
+            let f = rng.next_f32();
+            assert!((0.0..1.0).contains(&f));
+        }
+    }
+
+    #[test]
+    fn range_is_bounded_and_deterministic() {
+        fn run() -> Vec<u32> {
+            let mut rng = XorShift::new(7);
+            (0..100).map(|_| rng.range(10..20)).collect()
+        }
+        let values = run();
+        assert!(values.iter().all(|v| (10..20).contains(v)));
+        assert_eq!(values, run());
+    }
+}
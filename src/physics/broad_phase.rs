@@ -5,6 +5,230 @@
 
 use crate::math::Vec2;
 use super::collision::AABB;
+use super::Body;
+
+/// A broad phase reduces the O(n²) all-pairs collision scan to a short list of
+/// candidate pairs whose AABBs might overlap, which narrow phase then tests
+/// precisely. Implementations trade memory and setup cost against how well they
+/// exploit temporal coherence.
+pub trait BroadPhase {
+    /// Return the candidate overlapping pairs for this frame, each as
+    /// `(low_id, high_id)`, sorted and deduplicated. `bodies[i]`'s `layer`/
+    /// `mask` must be consulted for every candidate pair touching AABB `i`, so
+    /// a per-`Body` collision filter (e.g. "bullets don't collide with
+    /// bullets") holds regardless of which `BroadPhaseKind` is configured.
+    fn pairs(&mut self, bodies: &[Body], bounds: &[AABB]) -> Vec<(usize, usize)>;
+}
+
+/// Trivial O(n²) broad phase that emits every pair whose AABBs overlap. Kept as
+/// a correctness baseline and for tiny body counts where acceleration does not
+/// pay off.
+#[derive(Default)]
+pub struct BruteForce;
+
+impl BroadPhase for BruteForce {
+    fn pairs(&mut self, bodies: &[Body], bounds: &[AABB]) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for i in 0..bounds.len() {
+            for j in (i + 1)..bounds.len() {
+                if bounds[i].intersects(&bounds[j])
+                    && layers_collide(bodies[i].layer, bodies[i].mask, bodies[j].layer, bodies[j].mask)
+                {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+/// A collision layer/mask bitfield. An object lives on one or more `layer`
+/// bits and collides with the `layer`s named in its `mask`. Two objects `a`,
+/// `b` are a candidate pair only when `a.mask & b.layer != 0 && b.mask &
+/// a.layer != 0`, so rules like "bullets don't collide with bullets" or
+/// "player ignores its own platform" are expressed without post-filtering.
+pub type LayerMask = u32;
+
+/// Every layer — the default for the untyped API.
+pub const ALL_LAYERS: LayerMask = !0;
+
+/// Whether two (layer, mask) pairs may collide.
+#[inline]
+fn layers_collide(a_layer: LayerMask, a_mask: LayerMask, b_layer: LayerMask, b_mask: LayerMask) -> bool {
+    a_mask & b_layer != 0 && b_mask & a_layer != 0
+}
+
+/// Multi-resolution broad phase backed by a sorted array of 64-bit Morton
+/// keys. Each object is quantized into cells whose size is chosen from the
+/// log2 of the object's extent, so large objects occupy coarse cells and small
+/// ones fine cells. The key interleaves the cell's x/y (Morton order) and is
+/// tagged with the level, so sorting groups same-level cells together; pair
+/// generation scans runs of equal keys and walks each object's coarser-level
+/// ancestor cells. This avoids the O(k²) blow-up a flat grid suffers when many
+/// bodies pile into one cell.
+pub struct MortonBroadphase {
+    /// Edge length of a level-0 (finest) cell.
+    base_cell: f32,
+    /// One record per (object, covered cell). Rebuilt each frame.
+    records: Vec<Record>,
+    /// Highest level populated this frame, for the ancestor walk.
+    max_level: u8,
+}
+
+#[derive(Clone, Copy)]
+struct Record {
+    key: u64,
+    id: usize,
+    cx: i32,
+    cy: i32,
+    level: u8,
+    layer: LayerMask,
+    mask: LayerMask,
+}
+
+/// Cell coordinates are biased by this before interleaving so a symmetric range
+/// of negative world coordinates maps to non-negative Morton inputs.
+const MORTON_BIAS: i64 = 1 << 20;
+
+/// Interleave the low 21 bits of `x` and `y` into a 42-bit Morton code.
+#[inline]
+fn morton2(x: i32, y: i32) -> u64 {
+    let spread = |v: i32| -> u64 {
+        let mut n = ((v as i64 + MORTON_BIAS).clamp(0, (1 << 21) - 1)) as u64;
+        n &= 0x1f_ffff;
+        n = (n | (n << 16)) & 0x0000_ffff_0000_ffff;
+        n = (n | (n << 8)) & 0x00ff_00ff_00ff_00ff;
+        n = (n | (n << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+        n = (n | (n << 2)) & 0x3333_3333_3333_3333;
+        n = (n | (n << 1)) & 0x5555_5555_5555_5555;
+        n
+    };
+    spread(x) | (spread(y) << 1)
+}
+
+/// Pack a (level, cell) pair into a sortable key: level in the high bits keeps
+/// each level's cells contiguous once the array is sorted.
+#[inline]
+fn cell_key(level: u8, cx: i32, cy: i32) -> u64 {
+    ((level as u64) << 56) | morton2(cx, cy)
+}
+
+impl MortonBroadphase {
+    pub fn new(base_cell: f32) -> Self {
+        Self {
+            base_cell: base_cell.max(f32::MIN_POSITIVE),
+            records: Vec::new(),
+            max_level: 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.max_level = 0;
+    }
+
+    /// Level whose cell size first exceeds the object's largest extent, so an
+    /// object never spans more than about two cells on either axis.
+    fn level_for(&self, aabb: &AABB) -> u8 {
+        let extent = aabb.max - aabb.min;
+        let size = extent.x.max(extent.y).max(self.base_cell);
+        let ratio = size / self.base_cell;
+        (ratio.log2().ceil().max(0.0) as u8).min(31)
+    }
+
+    /// Insert an object with an explicit collision `layer`/`mask`.
+    pub fn insert(&mut self, id: usize, aabb: &AABB, layer: LayerMask, mask: LayerMask) {
+        let level = self.level_for(aabb);
+        self.max_level = self.max_level.max(level);
+        let cell = self.base_cell * (1u32 << level) as f32;
+        let inv = 1.0 / cell;
+        let min_x = (aabb.min.x * inv).floor() as i32;
+        let max_x = (aabb.max.x * inv).floor() as i32;
+        let min_y = (aabb.min.y * inv).floor() as i32;
+        let max_y = (aabb.max.y * inv).floor() as i32;
+        for cy in min_y..=max_y {
+            for cx in min_x..=max_x {
+                self.records.push(Record {
+                    key: cell_key(level, cx, cy),
+                    id,
+                    cx,
+                    cy,
+                    level,
+                    layer,
+                    mask,
+                });
+            }
+        }
+    }
+
+    /// Insert an object that collides with everything (untyped wrapper).
+    pub fn insert_untyped(&mut self, id: usize, aabb: &AABB) {
+        self.insert(id, aabb, ALL_LAYERS, ALL_LAYERS);
+    }
+
+    /// Generate candidate pairs. Same-level cell-mates come from runs of equal
+    /// keys; coarse/fine overlaps come from walking each record's ancestor cell
+    /// at every coarser level.
+    pub fn get_pairs(&mut self) -> Vec<(usize, usize)> {
+        self.records.sort_unstable_by_key(|r| r.key);
+
+        let mut pairs = Vec::new();
+
+        // Same level, same cell: every pair within a run of equal keys.
+        let mut start = 0;
+        while start < self.records.len() {
+            let mut end = start + 1;
+            while end < self.records.len() && self.records[end].key == self.records[start].key {
+                end += 1;
+            }
+            for i in start..end {
+                for j in (i + 1)..end {
+                    self.try_pair(&self.records[i], &self.records[j], &mut pairs);
+                }
+            }
+            start = end;
+        }
+
+        // Cross level: each record probes its ancestor cell at coarser levels.
+        for r in &self.records {
+            for level in (r.level + 1)..=self.max_level {
+                let shift = level - r.level;
+                let key = cell_key(level, r.cx >> shift, r.cy >> shift);
+                for other in self.run_for_key(key) {
+                    self.try_pair(r, other, &mut pairs);
+                }
+            }
+        }
+
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// All records sharing `key` (records are sorted by key).
+    fn run_for_key(&self, key: u64) -> &[Record] {
+        let lo = self.records.partition_point(|r| r.key < key);
+        let hi = self.records.partition_point(|r| r.key <= key);
+        &self.records[lo..hi]
+    }
+
+    #[inline]
+    fn try_pair(&self, a: &Record, b: &Record, pairs: &mut Vec<(usize, usize)>) {
+        if a.id != b.id && layers_collide(a.layer, a.mask, b.layer, b.mask) {
+            pairs.push((a.id.min(b.id), a.id.max(b.id)));
+        }
+    }
+}
+
+impl BroadPhase for MortonBroadphase {
+    fn pairs(&mut self, bodies: &[Body], bounds: &[AABB]) -> Vec<(usize, usize)> {
+        self.clear();
+        for (id, aabb) in bounds.iter().enumerate() {
+            self.insert(id, aabb, bodies[id].layer, bodies[id].mask);
+        }
+        self.get_pairs()
+    }
+}
 
 /// Simple grid-based spatial hash
 pub struct SpatialHash {
@@ -85,6 +309,8 @@ impl SpatialHash {
 /// Sweep and prune for 1D broad phase
 pub struct SweepAndPrune {
     endpoints: Vec<Endpoint>,
+    /// Per-id AABB kept for the y-interval overlap check during the sweep.
+    bounds: Vec<AABB>,
 }
 
 #[derive(Clone, Copy)]
@@ -98,9 +324,22 @@ impl SweepAndPrune {
     pub fn new() -> Self {
         Self {
             endpoints: Vec::new(),
+            bounds: Vec::new(),
         }
     }
-    
+
+    /// Insertion sort on the x-endpoints. The list is kept between frames, so
+    /// after a small time step it is nearly sorted and this runs in ~O(n).
+    fn insertion_sort(&mut self) {
+        for i in 1..self.endpoints.len() {
+            let mut j = i;
+            while j > 0 && self.endpoints[j - 1].value > self.endpoints[j].value {
+                self.endpoints.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
     pub fn update(&mut self, aabbs: &[(usize, AABB)]) {
         self.endpoints.clear();
         
@@ -140,3 +379,69 @@ impl Default for SweepAndPrune {
         Self::new()
     }
 }
+
+impl BroadPhase for SweepAndPrune {
+    fn pairs(&mut self, bodies: &[Body], bounds: &[AABB]) -> Vec<(usize, usize)> {
+        // Keep the endpoint list across frames for temporal coherence: rebuild
+        // only when the body count changes, otherwise refresh values in place
+        // so the near-sorted insertion sort stays cheap.
+        if self.endpoints.len() != bounds.len() * 2 {
+            self.endpoints.clear();
+            for (id, aabb) in bounds.iter().enumerate() {
+                self.endpoints.push(Endpoint { value: aabb.min.x, id, is_min: true });
+                self.endpoints.push(Endpoint { value: aabb.max.x, id, is_min: false });
+            }
+        } else {
+            for endpoint in &mut self.endpoints {
+                let aabb = &bounds[endpoint.id];
+                endpoint.value = if endpoint.is_min { aabb.min.x } else { aabb.max.x };
+            }
+        }
+        self.bounds = bounds.to_vec();
+        self.insertion_sort();
+
+        let mut pairs = Vec::new();
+        let mut active: Vec<usize> = Vec::new();
+        for endpoint in &self.endpoints {
+            if endpoint.is_min {
+                let a = &self.bounds[endpoint.id];
+                for &other in &active {
+                    // x-intervals already overlap (both in the active set); add
+                    // a cheap y-interval check before emitting the pair.
+                    let b = &self.bounds[other];
+                    if a.min.y <= b.max.y
+                        && a.max.y >= b.min.y
+                        && layers_collide(
+                            bodies[endpoint.id].layer,
+                            bodies[endpoint.id].mask,
+                            bodies[other].layer,
+                            bodies[other].mask,
+                        )
+                    {
+                        pairs.push((endpoint.id.min(other), endpoint.id.max(other)));
+                    }
+                }
+                active.push(endpoint.id);
+            } else if let Some(pos) = active.iter().position(|&x| x == endpoint.id) {
+                active.swap_remove(pos);
+            }
+        }
+
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+}
+
+impl BroadPhase for SpatialHash {
+    fn pairs(&mut self, bodies: &[Body], bounds: &[AABB]) -> Vec<(usize, usize)> {
+        self.clear();
+        for (id, aabb) in bounds.iter().enumerate() {
+            self.insert(id, aabb);
+        }
+        self.get_pairs()
+            .into_iter()
+            .filter(|&(a, b)| layers_collide(bodies[a].layer, bodies[a].mask, bodies[b].layer, bodies[b].mask))
+            .collect()
+    }
+}
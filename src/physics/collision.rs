@@ -4,7 +4,7 @@
 //! ASM: AABB tests, circle tests, SAT (narrow phase hot paths)
 
 use crate::math::Vec2;
-use super::{Body, Contact};
+use super::{Body, Contact, Shape};
 
 #[cfg(not(no_asm))]
 extern "C" {
@@ -145,16 +145,324 @@ pub fn aabb_vs_aabb(a: &AABB, b: &AABB) -> Option<Contact> {
     })
 }
 
-/// Detect collisions between all bodies (simple O(n²) for now)
+/// Result of a swept AABB test: the fraction of the displacement travelled
+/// before impact (`toi` in `[0, 1]`) and the surface normal at the point of
+/// contact.
+#[derive(Clone, Copy, Debug)]
+pub struct Sweep {
+    pub toi: f32,
+    pub normal: Vec2,
+}
+
+/// Continuous (swept) AABB-vs-static-AABB test. A moving box centred at
+/// `origin` with half-extents `half` travels by `d = velocity * dt` against a
+/// static box (`static_center`, `static_half`).
+///
+/// The static box is inflated by the mover's half-extents (Minkowski sum) so
+/// the mover can be treated as a point. Entry/exit times are computed per axis
+/// (`t = (edge - origin) / d`, guarding `d == 0` with an infinite interval);
+/// impact occurs when `t_entry <= t_exit`, `t_entry` lies in `[0, 1]`, and the
+/// box was not already separated on an axis. The normal comes from whichever
+/// axis produced the latest entry. Returns `None` when they never meet within
+/// the step.
+pub fn swept_aabb(
+    origin: Vec2,
+    half: Vec2,
+    d: Vec2,
+    static_center: Vec2,
+    static_half: Vec2,
+) -> Option<Sweep> {
+    let expand = static_half + half;
+    let min = static_center - expand;
+    let max = static_center + expand;
+
+    // Per-axis entry/exit fractions.
+    let (x_entry, x_exit) = axis_times(origin.x, d.x, min.x, max.x)?;
+    let (y_entry, y_exit) = axis_times(origin.y, d.y, min.y, max.y)?;
+
+    let t_entry = x_entry.max(y_entry);
+    let t_exit = x_exit.min(y_exit);
+
+    if t_entry > t_exit || t_entry < 0.0 || t_entry > 1.0 {
+        return None;
+    }
+
+    let normal = if x_entry > y_entry {
+        Vec2::new(-d.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, -d.y.signum())
+    };
+
+    Some(Sweep { toi: t_entry, normal })
+}
+
+/// Entry/exit fractions for one axis, or `None` if the mover is stationary on
+/// this axis and already outside the slab (it can never enter).
+fn axis_times(origin: f32, d: f32, min: f32, max: f32) -> Option<(f32, f32)> {
+    if d == 0.0 {
+        if origin < min || origin > max {
+            return None;
+        }
+        return Some((f32::NEG_INFINITY, f32::INFINITY));
+    }
+    let t1 = (min - origin) / d;
+    let t2 = (max - origin) / d;
+    Some((t1.min(t2), t1.max(t2)))
+}
+
+/// Circle vs AABB collision. Finds the closest point on the box to the circle
+/// centre; they touch when it lies within one radius.
+pub fn circle_vs_aabb(circle: &Circle, aabb: &AABB) -> Option<Contact> {
+    let closest = Vec2::new(
+        circle.center.x.clamp(aabb.min.x, aabb.max.x),
+        circle.center.y.clamp(aabb.min.y, aabb.max.y),
+    );
+    let diff = circle.center - closest;
+    let dist_sq = diff.length_squared();
+
+    if dist_sq >= circle.radius * circle.radius {
+        return None;
+    }
+
+    let dist = dist_sq.sqrt();
+    // When the centre is inside the box, push out along the box's up axis.
+    let normal = if dist > 0.0 {
+        diff / dist
+    } else {
+        Vec2::UP
+    };
+
+    Some(Contact {
+        body_a: 0,
+        body_b: 0,
+        normal: -normal,
+        penetration: circle.radius - dist,
+        point: closest,
+    })
+}
+
+/// Convex polygon collider with counter-clockwise vertices. Oriented boxes are
+/// the 4-vertex special case (see [`Polygon::obb`]); arbitrary convex hulls are
+/// supported by the SAT tests below and can be passed to them directly even
+/// though [`Body`] only stores the fixed-size shapes in [`Shape`].
+#[derive(Clone, Debug)]
+pub struct Polygon {
+    pub vertices: Vec<Vec2>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Vec2>) -> Self {
+        Self { vertices }
+    }
+
+    /// Oriented box: `half_extents` rotated by `angle` (radians) about `center`,
+    /// wound counter-clockwise.
+    pub fn obb(center: Vec2, half_extents: Vec2, angle: f32) -> Self {
+        let (s, c) = angle.sin_cos();
+        let corners = [
+            Vec2::new(-half_extents.x, -half_extents.y),
+            Vec2::new(half_extents.x, -half_extents.y),
+            Vec2::new(half_extents.x, half_extents.y),
+            Vec2::new(-half_extents.x, half_extents.y),
+        ];
+        let vertices = corners
+            .iter()
+            .map(|p| center + Vec2::new(p.x * c - p.y * s, p.x * s + p.y * c))
+            .collect();
+        Self { vertices }
+    }
+
+    /// Candidate separating axes: the unit normal of each edge (inward-facing
+    /// for CCW winding; SAT only needs the axis direction).
+    fn axes(&self) -> Vec<Vec2> {
+        let n = self.vertices.len();
+        (0..n)
+            .map(|i| {
+                let edge = self.vertices[(i + 1) % n] - self.vertices[i];
+                Vec2::new(-edge.y, edge.x).normalize()
+            })
+            .collect()
+    }
+
+    /// Project every vertex onto `axis`, returning the `[min, max]` interval.
+    fn project(&self, axis: Vec2) -> (f32, f32) {
+        let mut min = f32::INFINITY;
+        let mut max = f32::NEG_INFINITY;
+        for v in &self.vertices {
+            let d = v.dot(axis);
+            min = min.min(d);
+            max = max.max(d);
+        }
+        (min, max)
+    }
+
+    fn centroid(&self) -> Vec2 {
+        let sum = self
+            .vertices
+            .iter()
+            .copied()
+            .fold(Vec2::ZERO, |acc, v| acc + v);
+        sum / self.vertices.len().max(1) as f32
+    }
+}
+
+/// Polygon vs polygon via the Separating Axis Theorem. The candidate axes are
+/// the edge normals of both shapes; if any axis shows a gap there is no
+/// collision, otherwise the axis of minimum overlap gives the `normal`
+/// (flipped to point from A to B) and that overlap is the `penetration`.
+pub fn polygon_vs_polygon(a: &Polygon, b: &Polygon) -> Option<Contact> {
+    let mut best_overlap = f32::INFINITY;
+    let mut best_axis = Vec2::UP;
+
+    for axis in a.axes().into_iter().chain(b.axes()) {
+        let (amin, amax) = a.project(axis);
+        let (bmin, bmax) = b.project(axis);
+        if amax < bmin || bmax < amin {
+            return None; // Separating axis found.
+        }
+        let overlap = amax.min(bmax) - amin.max(bmin);
+        if overlap < best_overlap {
+            best_overlap = overlap;
+            best_axis = axis;
+        }
+    }
+
+    let normal = oriented(best_axis, b.centroid() - a.centroid());
+    // Deepest vertex of A along the contact normal.
+    let point = a
+        .vertices
+        .iter()
+        .copied()
+        .max_by(|p, q| {
+            p.dot(normal)
+                .partial_cmp(&q.dot(normal))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or_else(|| a.centroid());
+
+    Some(Contact {
+        body_a: 0,
+        body_b: 0,
+        normal,
+        penetration: best_overlap,
+        point,
+    })
+}
+
+/// Polygon vs circle via SAT, adding the axis from the closest polygon vertex
+/// to the circle centre to the edge normals.
+pub fn polygon_vs_circle(poly: &Polygon, circle: &Circle) -> Option<Contact> {
+    let mut axes = poly.axes();
+    if let Some(closest) = poly.vertices.iter().copied().min_by(|p, q| {
+        p.distance_squared(circle.center)
+            .partial_cmp(&q.distance_squared(circle.center))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }) {
+        let to_center = circle.center - closest;
+        if to_center.length_squared() > 0.0 {
+            axes.push(to_center.normalize());
+        }
+    }
+
+    let mut best_overlap = f32::INFINITY;
+    let mut best_axis = Vec2::UP;
+    for axis in axes {
+        let (pmin, pmax) = poly.project(axis);
+        let c = circle.center.dot(axis);
+        let (cmin, cmax) = (c - circle.radius, c + circle.radius);
+        if pmax < cmin || cmax < pmin {
+            return None;
+        }
+        let overlap = pmax.min(cmax) - pmin.max(cmin);
+        if overlap < best_overlap {
+            best_overlap = overlap;
+            best_axis = axis;
+        }
+    }
+
+    let normal = oriented(best_axis, circle.center - poly.centroid());
+    Some(Contact {
+        body_a: 0,
+        body_b: 0,
+        normal,
+        penetration: best_overlap,
+        point: circle.center - normal * circle.radius,
+    })
+}
+
+/// Flip `axis` so it points in the same half-plane as `direction` (A→B).
+fn oriented(axis: Vec2, direction: Vec2) -> Vec2 {
+    if direction.dot(axis) < 0.0 {
+        -axis
+    } else {
+        axis
+    }
+}
+
+/// Narrow-phase test between two bodies, dispatching on their shapes. Returns a
+/// contact with `body_a`/`body_b` left at `0` for the caller to fill in.
+fn narrow_phase(a: &Body, b: &Body) -> Option<Contact> {
+    match (a.shape, b.shape) {
+        (Shape::Circle { radius: ra }, Shape::Circle { radius: rb }) => {
+            circle_vs_circle(&Circle::new(a.position, ra), &Circle::new(b.position, rb))
+        }
+        (Shape::Aabb { half_extents: ha }, Shape::Aabb { half_extents: hb }) => aabb_vs_aabb(
+            &AABB::from_center(a.position, ha),
+            &AABB::from_center(b.position, hb),
+        ),
+        (Shape::Circle { radius }, Shape::Aabb { half_extents }) => circle_vs_aabb(
+            &Circle::new(a.position, radius),
+            &AABB::from_center(b.position, half_extents),
+        ),
+        (Shape::Aabb { half_extents }, Shape::Circle { radius }) => {
+            // Test circle-vs-box then flip the normal back to point A→B.
+            circle_vs_aabb(
+                &Circle::new(b.position, radius),
+                &AABB::from_center(a.position, half_extents),
+            )
+            .map(|mut c| {
+                c.normal = -c.normal;
+                c
+            })
+        }
+        // Any pairing involving an oriented box goes through the polygon SAT path.
+        _ => narrow_phase_sat(a, b),
+    }
+}
+
+/// SAT narrow phase for shape pairs that include an oriented box: each box is
+/// converted to a polygon, circles stay circles.
+fn narrow_phase_sat(a: &Body, b: &Body) -> Option<Contact> {
+    let poly_of = |shape: Shape, pos: Vec2| -> Option<Polygon> {
+        match shape {
+            Shape::Aabb { half_extents } => Some(Polygon::obb(pos, half_extents, 0.0)),
+            Shape::Obb { half_extents, angle } => Some(Polygon::obb(pos, half_extents, angle)),
+            Shape::Circle { .. } => None,
+        }
+    };
+
+    match (poly_of(a.shape, a.position), poly_of(b.shape, b.position)) {
+        (Some(pa), Some(pb)) => polygon_vs_polygon(&pa, &pb),
+        (Some(pa), None) => {
+            let Shape::Circle { radius } = b.shape else { return None };
+            polygon_vs_circle(&pa, &Circle::new(b.position, radius))
+        }
+        (None, Some(pb)) => {
+            let Shape::Circle { radius } = a.shape else { return None };
+            polygon_vs_circle(&pb, &Circle::new(a.position, radius)).map(|mut c| {
+                c.normal = -c.normal;
+                c
+            })
+        }
+        (None, None) => None,
+    }
+}
+
+/// Detect collisions between all bodies (simple O(n²) fallback).
 pub fn detect_collisions(bodies: &[Body], contacts: &mut Vec<Contact>) {
-    let radius = 10.0; // Default radius for now
-    
     for i in 0..bodies.len() {
         for j in (i + 1)..bodies.len() {
-            let a = Circle::new(bodies[i].position, radius);
-            let b = Circle::new(bodies[j].position, radius);
-            
-            if let Some(mut contact) = circle_vs_circle(&a, &b) {
+            if let Some(mut contact) = narrow_phase(&bodies[i], &bodies[j]) {
                 contact.body_a = i;
                 contact.body_b = j;
                 contacts.push(contact);
@@ -163,41 +471,104 @@ pub fn detect_collisions(bodies: &[Body], contacts: &mut Vec<Contact>) {
     }
 }
 
+/// Narrow-phase only the candidate pairs produced by a broad phase, instead of
+/// the full O(n²) scan. `pairs` must already be ordered `(low, high)`.
+pub fn detect_collisions_pairs(
+    bodies: &[Body],
+    pairs: &[(usize, usize)],
+    contacts: &mut Vec<Contact>,
+) {
+    for &(i, j) in pairs {
+        if let Some(mut contact) = narrow_phase(&bodies[i], &bodies[j]) {
+            contact.body_a = i;
+            contact.body_b = j;
+            contacts.push(contact);
+        }
+    }
+}
+
 /// Resolve contact constraints
 pub fn resolve_contacts(bodies: &mut [Body], contacts: &[Contact]) {
     for contact in contacts {
-        let (a_inv_mass, b_inv_mass, a_restitution, b_restitution, relative_vel);
-        {
-            let a = &bodies[contact.body_a];
-            let b = &bodies[contact.body_b];
-            
-            if a.is_static() && b.is_static() {
-                continue;
-            }
-            
-            a_inv_mass = a.inv_mass;
-            b_inv_mass = b.inv_mass;
-            a_restitution = a.restitution;
-            b_restitution = b.restitution;
-            relative_vel = b.velocity - a.velocity;
-        }
-        
-        let vel_along_normal = relative_vel.dot(contact.normal);
-        
-        if vel_along_normal > 0.0 {
-            continue;
-        }
-        
-        let e = a_restitution.min(b_restitution);
-        let j = -(1.0 + e) * vel_along_normal / (a_inv_mass + b_inv_mass);
-        let impulse = contact.normal * j;
-        
-        bodies[contact.body_a].velocity -= impulse * a_inv_mass;
-        bodies[contact.body_b].velocity += impulse * b_inv_mass;
-        
-        // Position correction
-        let correction = contact.normal * (contact.penetration * 0.8 / (a_inv_mass + b_inv_mass));
-        bodies[contact.body_a].position -= correction * a_inv_mass;
-        bodies[contact.body_b].position += correction * b_inv_mass;
+        resolve_one(bodies, contact);
+    }
+}
+
+/// Resolve contacts exactly like [`resolve_contacts`], additionally pushing a
+/// [`CollisionEvent`] for each pair whose normal impulse was non-zero. Run this
+/// on the final solver iteration so the recorded impulse reflects the settled
+/// contact rather than an intermediate pass.
+pub fn resolve_contacts_events(
+    bodies: &mut [Body],
+    contacts: &[Contact],
+    events: &mut Vec<super::CollisionEvent>,
+) {
+    for contact in contacts {
+        if let Some(impulse) = resolve_one(bodies, contact).filter(|&j| j != 0.0) {
+            events.push(super::CollisionEvent {
+                a: contact.body_a,
+                b: contact.body_b,
+                normal: contact.normal,
+                impulse,
+            });
+        }
+    }
+}
+
+/// Resolve a single contact with combined restitution and Coulomb friction,
+/// applying positional correction. Returns the normal impulse magnitude, or
+/// `None` when the pair was skipped (both static, or separating).
+fn resolve_one(bodies: &mut [Body], contact: &Contact) -> Option<f32> {
+    let (a_inv_mass, b_inv_mass, a_restitution, b_restitution, a_friction, b_friction, relative_vel);
+    {
+        let a = &bodies[contact.body_a];
+        let b = &bodies[contact.body_b];
+
+        if a.is_static() && b.is_static() {
+            return None;
+        }
+
+        a_inv_mass = a.inv_mass;
+        b_inv_mass = b.inv_mass;
+        a_restitution = a.restitution;
+        b_restitution = b.restitution;
+        a_friction = a.friction;
+        b_friction = b.friction;
+        relative_vel = b.velocity - a.velocity;
     }
+
+    let vel_along_normal = relative_vel.dot(contact.normal);
+
+    if vel_along_normal > 0.0 {
+        return None;
+    }
+
+    let inv_mass_sum = a_inv_mass + b_inv_mass;
+    let e = a_restitution.min(b_restitution);
+    let j = -(1.0 + e) * vel_along_normal / inv_mass_sum;
+    let impulse = contact.normal * j;
+
+    bodies[contact.body_a].velocity -= impulse * a_inv_mass;
+    bodies[contact.body_b].velocity += impulse * b_inv_mass;
+
+    // Coulomb friction along the tangent, clamped to the friction cone
+    // (|jt| <= mu * |jn|). The friction coefficient is the geometric mean of
+    // the pair; restitution above takes the pair's minimum instead.
+    let tangent = relative_vel - contact.normal * vel_along_normal;
+    if tangent.length_squared() > 1e-6 {
+        let tangent = tangent.normalize();
+        let jt = -relative_vel.dot(tangent) / inv_mass_sum;
+        let mu = (a_friction * b_friction).sqrt();
+        let jt = jt.clamp(-j.abs() * mu, j.abs() * mu);
+        let friction_impulse = tangent * jt;
+        bodies[contact.body_a].velocity -= friction_impulse * a_inv_mass;
+        bodies[contact.body_b].velocity += friction_impulse * b_inv_mass;
+    }
+
+    // Position correction
+    let correction = contact.normal * (contact.penetration * 0.8 / inv_mass_sum);
+    bodies[contact.body_a].position -= correction * a_inv_mass;
+    bodies[contact.body_b].position += correction * b_inv_mass;
+
+    Some(j)
 }
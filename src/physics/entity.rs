@@ -0,0 +1,93 @@
+//! Shared physical-entity abstraction.
+//!
+//! Rust: trait-level integration shared by every moving object (fighters,
+//! projectiles, NPCs). Keeping velocity integration and ground resolution in
+//! one default method means adding a new moving object is a matter of
+//! implementing [`PhysicalEntity`] and pointing the accessors at its fields.
+
+use crate::math::FixedPoint;
+
+/// Axis-aligned box in fixed-point world space, anchored at its top-left
+/// corner. Used for both hit detection and rendering bounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub x: FixedPoint,
+    pub y: FixedPoint,
+    pub width: FixedPoint,
+    pub height: FixedPoint,
+}
+
+impl Bounds {
+    pub fn new(x: FixedPoint, y: FixedPoint, width: FixedPoint, height: FixedPoint) -> Self {
+        Self { x, y, width, height }
+    }
+}
+
+/// A moving object integrated deterministically each fixed step.
+///
+/// Implementors expose their position/velocity fields through the accessors
+/// and mutators; the default [`apply_physics`](PhysicalEntity::apply_physics)
+/// then owns velocity integration, gravity, and ground resolution so that code
+/// lives in exactly one place. Entities that fly freely (e.g. straight-line
+/// projectiles) leave [`gravity`](PhysicalEntity::gravity) and
+/// [`ground`](PhysicalEntity::ground) at their defaults and simply integrate.
+pub trait PhysicalEntity {
+    fn x(&self) -> FixedPoint;
+    fn y(&self) -> FixedPoint;
+    fn vel_x(&self) -> FixedPoint;
+    fn vel_y(&self) -> FixedPoint;
+
+    fn set_x(&mut self, x: FixedPoint);
+    fn set_y(&mut self, y: FixedPoint);
+    fn set_vel_x(&mut self, vel_x: FixedPoint);
+    fn set_vel_y(&mut self, vel_y: FixedPoint);
+
+    /// Bounds used for collision / hit detection.
+    fn hit_bounds(&self) -> Bounds;
+
+    /// Bounds used when drawing the entity.
+    fn display_bounds(&self) -> Bounds;
+
+    /// Downward acceleration applied each step while airborne. Defaults to
+    /// `ZERO` for entities that ignore gravity.
+    fn gravity(&self) -> FixedPoint {
+        FixedPoint::ZERO
+    }
+
+    /// Ground plane the entity rests on, or `None` if it never collides with
+    /// the ground and integrates freely.
+    fn ground(&self) -> Option<FixedPoint> {
+        None
+    }
+
+    /// Whether the entity is currently resting on the ground.
+    fn grounded(&self) -> bool {
+        false
+    }
+
+    /// Record the result of ground resolution. No-op for entities that do not
+    /// track a grounded flag.
+    fn set_grounded(&mut self, _grounded: bool) {}
+
+    /// Integrate velocity, apply gravity while airborne, and resolve ground
+    /// collision. Shared by every moving object.
+    fn apply_physics(&mut self) {
+        if !self.grounded() {
+            let gravity = self.gravity();
+            if gravity != FixedPoint::ZERO {
+                self.set_vel_y(self.vel_y() + gravity);
+            }
+        }
+
+        self.set_x(self.x() + self.vel_x());
+        self.set_y(self.y() + self.vel_y());
+
+        if let Some(ground) = self.ground() {
+            if self.y() >= ground {
+                self.set_y(ground);
+                self.set_vel_y(FixedPoint::ZERO);
+                self.set_grounded(true);
+            }
+        }
+    }
+}
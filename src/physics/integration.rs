@@ -3,6 +3,7 @@
 //! ASM accelerates the integration step for large body counts.
 
 use super::Body;
+use crate::math::{FixedPoint, FixedVec2};
 
 #[cfg(not(no_asm))]
 extern "C" {
@@ -63,14 +64,167 @@ pub fn integrate_verlet(bodies: &mut [Body], prev_positions: &mut [crate::math::
         if body.is_static() {
             continue;
         }
-        
+
         let temp = body.position;
         let velocity = body.position - prev_positions[i];
-        
+
         body.position = body.position + velocity + body.acceleration * dt * dt;
         prev_positions[i] = temp;
-        
+
         body.acceleration.x = 0.0;
         body.acceleration.y = 0.0;
     }
 }
+
+/// Which integration path [`PhysicsWorld::step`](super::PhysicsWorld::step)
+/// takes. `Float` is the fast `f32` default; `Fixed` routes through the 16.16
+/// fixed-point path so lockstep peers stay bit-identical.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum IntegrationMode {
+    #[default]
+    Float,
+    Fixed,
+}
+
+/// A single body reduced to the motion state the integrator needs, in 16.16
+/// fixed point. Products of `velocity * dt` / `acceleration * dt` go through
+/// [`FixedPoint::mul_full`], so integration is deterministic on any CPU.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FixedBody {
+    pub position: FixedVec2,
+    pub velocity: FixedVec2,
+    pub acceleration: FixedVec2,
+    pub inv_mass: FixedPoint,
+}
+
+impl FixedBody {
+    /// Snapshot a float [`Body`]'s motion state into fixed point.
+    pub fn from_body(body: &Body) -> Self {
+        Self {
+            position: FixedVec2::from_vec2(body.position),
+            velocity: FixedVec2::from_vec2(body.velocity),
+            acceleration: FixedVec2::from_vec2(body.acceleration),
+            inv_mass: FixedPoint::from_f32(body.inv_mass),
+        }
+    }
+
+    /// Write the integrated motion state back onto a float [`Body`], leaving its
+    /// mass/material/shape fields untouched.
+    pub fn write_back(&self, body: &mut Body) {
+        body.position = self.position.to_vec2();
+        body.velocity = self.velocity.to_vec2();
+        body.acceleration = self.acceleration.to_vec2();
+    }
+
+    #[inline]
+    pub fn is_static(&self) -> bool {
+        self.inv_mass == FixedPoint::ZERO
+    }
+}
+
+/// Deterministic semi-implicit Euler on fixed-point bodies; the counterpart to
+/// [`integrate_bodies`]. Clears acceleration after the step, like the float
+/// path.
+pub fn integrate_bodies_fixed(bodies: &mut [FixedBody], dt: FixedPoint) {
+    for body in bodies.iter_mut() {
+        if body.is_static() {
+            continue;
+        }
+
+        body.velocity += body.acceleration * dt;
+        body.position += body.velocity * dt;
+        body.acceleration = FixedVec2::ZERO;
+    }
+}
+
+/// Deterministic Verlet integration on fixed-point bodies; the counterpart to
+/// [`integrate_verlet`].
+pub fn integrate_verlet_fixed(
+    bodies: &mut [FixedBody],
+    prev_positions: &mut [FixedVec2],
+    dt: FixedPoint,
+) {
+    let dt_sq = dt.mul_full(dt);
+    for (i, body) in bodies.iter_mut().enumerate() {
+        if body.is_static() {
+            continue;
+        }
+
+        let temp = body.position;
+        let velocity = body.position - prev_positions[i];
+        body.position = body.position + velocity + body.acceleration * dt_sq;
+        prev_positions[i] = temp;
+        body.acceleration = FixedVec2::ZERO;
+    }
+}
+
+/// Integrate a float [`Body`] slice under the selected [`IntegrationMode`]. The
+/// fixed path converts in and out of [`FixedBody`] around a deterministic
+/// integration step.
+pub fn integrate_bodies_mode(bodies: &mut [Body], dt: f32, mode: IntegrationMode) {
+    match mode {
+        IntegrationMode::Float => integrate_bodies(bodies, dt),
+        IntegrationMode::Fixed => {
+            let dt_fx = FixedPoint::from_f32(dt);
+            let mut fixed: Vec<FixedBody> = bodies.iter().map(FixedBody::from_body).collect();
+            integrate_bodies_fixed(&mut fixed, dt_fx);
+            for (body, f) in bodies.iter_mut().zip(&fixed) {
+                f.write_back(body);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two runs that start from identical fixed-point seeds must produce
+    /// identical raw `i32` state after many steps — the property that makes
+    /// lockstep networking possible.
+    #[test]
+    fn fixed_integration_is_bit_identical() {
+        fn run() -> Vec<(i32, i32)> {
+            let mut bodies = vec![
+                FixedBody {
+                    position: FixedVec2::from_ints(0, 0),
+                    velocity: FixedVec2::from_ints(3, -2),
+                    acceleration: FixedVec2::from_ints(0, 10),
+                    inv_mass: FixedPoint::ONE,
+                },
+                FixedBody {
+                    position: FixedVec2::from_ints(50, 20),
+                    velocity: FixedVec2::from_ints(-1, 4),
+                    acceleration: FixedVec2::from_ints(2, 10),
+                    inv_mass: FixedPoint::ONE,
+                },
+            ];
+            let dt = FixedPoint::from_f32(1.0 / 60.0);
+            for _ in 0..1000 {
+                // Re-apply a constant force each step, mirroring gravity.
+                for b in &mut bodies {
+                    b.acceleration = FixedVec2::from_ints(0, 10);
+                }
+                integrate_bodies_fixed(&mut bodies, dt);
+            }
+            bodies
+                .iter()
+                .map(|b| (b.position.x.raw(), b.position.y.raw()))
+                .collect()
+        }
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn static_fixed_body_does_not_move() {
+        let mut bodies = vec![FixedBody {
+            position: FixedVec2::from_ints(10, 10),
+            velocity: FixedVec2::from_ints(5, 5),
+            acceleration: FixedVec2::from_ints(0, 10),
+            inv_mass: FixedPoint::ZERO,
+        }];
+        integrate_bodies_fixed(&mut bodies, FixedPoint::from_f32(1.0 / 60.0));
+        assert_eq!(bodies[0].position, FixedVec2::from_ints(10, 10));
+    }
+}
@@ -6,17 +6,52 @@
 pub mod collision;
 pub mod integration;
 pub mod broad_phase;
+pub mod entity;
+pub mod triangulation;
 
 pub use collision::*;
+pub use entity::{Bounds, PhysicalEntity};
+pub use broad_phase::{BroadPhase, LayerMask, ALL_LAYERS};
+pub use integration::IntegrationMode;
 
 use crate::math::Vec2;
 
+/// Which broad-phase strategy `PhysicsWorld::step` uses to generate candidate
+/// collision pairs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BroadPhaseKind {
+    /// O(n²) all-pairs baseline.
+    BruteForce,
+    /// Persistent sorted endpoint list, ~O(n) with temporal coherence.
+    SweepAndPrune,
+    /// Uniform grid spatial hash.
+    SpatialHash,
+    /// Multi-resolution Morton-key index with collision layers/masks.
+    Morton,
+}
+
+impl BroadPhaseKind {
+    fn build(self) -> Box<dyn BroadPhase> {
+        match self {
+            BroadPhaseKind::BruteForce => Box::new(broad_phase::BruteForce),
+            BroadPhaseKind::SweepAndPrune => Box::new(broad_phase::SweepAndPrune::new()),
+            // Cell size ≈ average body diameter (default radius 10).
+            BroadPhaseKind::SpatialHash => Box::new(broad_phase::SpatialHash::new(20.0)),
+            // Finest cell ≈ average body diameter; larger bodies climb levels.
+            BroadPhaseKind::Morton => Box::new(broad_phase::MortonBroadphase::new(20.0)),
+        }
+    }
+}
+
 /// Physics world configuration
 #[derive(Clone, Debug)]
 pub struct PhysicsConfig {
     pub gravity: Vec2,
     pub iterations: u32,
     pub substeps: u32,
+    pub broad_phase: BroadPhaseKind,
+    /// Float (default) or deterministic fixed-point integration.
+    pub integration: IntegrationMode,
 }
 
 impl Default for PhysicsConfig {
@@ -25,10 +60,48 @@ impl Default for PhysicsConfig {
             gravity: Vec2::new(0.0, 980.0),
             iterations: 8,
             substeps: 1,
+            broad_phase: BroadPhaseKind::SweepAndPrune,
+            integration: IntegrationMode::Float,
+        }
+    }
+}
+
+/// The collider a [`Body`] carries, so the narrow phase no longer assumes a
+/// fixed radius for every body.
+#[derive(Clone, Copy, Debug)]
+pub enum Shape {
+    Circle { radius: f32 },
+    Aabb { half_extents: Vec2 },
+    /// Oriented box: `half_extents` rotated by `angle` (radians). Resolved
+    /// through the polygon SAT narrow phase.
+    Obb { half_extents: Vec2, angle: f32 },
+}
+
+impl Shape {
+    /// Half-extents of the shape's axis-aligned bounding box, used to build the
+    /// broad-phase [`AABB`].
+    pub fn half_extents(&self) -> Vec2 {
+        match *self {
+            Shape::Circle { radius } => Vec2::splat(radius),
+            Shape::Aabb { half_extents } => half_extents,
+            Shape::Obb { half_extents, angle } => {
+                // Bounding box of the rotated box.
+                let (s, c) = angle.sin_cos();
+                Vec2::new(
+                    half_extents.x * c.abs() + half_extents.y * s.abs(),
+                    half_extents.x * s.abs() + half_extents.y * c.abs(),
+                )
+            }
         }
     }
 }
 
+impl Default for Shape {
+    fn default() -> Self {
+        Shape::Circle { radius: 10.0 }
+    }
+}
+
 /// Physics body
 #[derive(Clone, Copy, Debug)]
 pub struct Body {
@@ -39,6 +112,14 @@ pub struct Body {
     pub inv_mass: f32,
     pub restitution: f32,
     pub friction: f32,
+    pub shape: Shape,
+    /// Collision layer this body lives on, and the mask of layers it collides
+    /// with (see [`broad_phase::LayerMask`]). Defaults to
+    /// [`ALL_LAYERS`]/[`ALL_LAYERS`] so existing bodies keep colliding with
+    /// everything unless a game opts into filtering via
+    /// [`with_layer_mask`](Body::with_layer_mask).
+    pub layer: LayerMask,
+    pub mask: LayerMask,
 }
 
 impl Default for Body {
@@ -51,6 +132,9 @@ impl Default for Body {
             inv_mass: 1.0,
             restitution: 0.5,
             friction: 0.3,
+            shape: Shape::default(),
+            layer: ALL_LAYERS,
+            mask: ALL_LAYERS,
         }
     }
 }
@@ -64,7 +148,7 @@ impl Body {
             ..Default::default()
         }
     }
-    
+
     pub fn static_body(position: Vec2) -> Self {
         Self {
             position,
@@ -73,7 +157,22 @@ impl Body {
             ..Default::default()
         }
     }
-    
+
+    /// Builder override for the body's collider shape.
+    pub fn with_shape(mut self, shape: Shape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Builder override for the body's collision `layer`/`mask`, e.g. so
+    /// projectiles can be put on their own layer and masked off from each
+    /// other ("bullets don't collide with bullets").
+    pub fn with_layer_mask(mut self, layer: LayerMask, mask: LayerMask) -> Self {
+        self.layer = layer;
+        self.mask = mask;
+        self
+    }
+
     pub fn is_static(&self) -> bool {
         self.inv_mass == 0.0
     }
@@ -84,59 +183,290 @@ pub struct PhysicsWorld {
     pub config: PhysicsConfig,
     pub bodies: Vec<Body>,
     contacts: Vec<Contact>,
+    broad_phase: Box<dyn BroadPhase>,
+    bounds: Vec<AABB>,
+    /// Contacts that produced an impulse this step, awaiting drainage by the
+    /// game layer. Cleared at the start of every [`simulate`](Self::simulate).
+    collision_events: Vec<CollisionEvent>,
+    /// Monotonic fixed-step counter, carried into each [`WorldSnapshot`].
+    frame: u64,
 }
 
 impl PhysicsWorld {
     pub fn new() -> Self {
-        Self {
-            config: PhysicsConfig::default(),
-            bodies: Vec::new(),
-            contacts: Vec::new(),
-        }
+        Self::with_config(PhysicsConfig::default())
     }
-    
+
     pub fn with_config(config: PhysicsConfig) -> Self {
+        let broad_phase = config.broad_phase.build();
         Self {
             config,
             bodies: Vec::new(),
             contacts: Vec::new(),
+            broad_phase,
+            bounds: Vec::new(),
+            collision_events: Vec::new(),
+            frame: 0,
         }
     }
-    
+
     pub fn add_body(&mut self, body: Body) -> usize {
         let id = self.bodies.len();
         self.bodies.push(body);
         id
     }
-    
+
     pub fn step(&mut self) {
         let dt = 1.0 / 60.0 / self.config.substeps as f32;
-        
         for _ in 0..self.config.substeps {
-            // Apply gravity
-            for body in &mut self.bodies {
-                if !body.is_static() {
-                    body.acceleration = self.config.gravity;
-                }
+            self.simulate(dt);
+        }
+        self.frame += 1;
+    }
+
+    /// Advance exactly one deterministic fixed step of size `dt`. Used by the
+    /// rollback driver, which controls the timestep explicitly rather than
+    /// leaning on the built-in substep schedule.
+    pub fn step_dt(&mut self, dt: f32) {
+        self.simulate(dt);
+        self.frame += 1;
+    }
+
+    /// The shared body of one integration+collision pass. Iterates bodies in
+    /// index order throughout so two machines produce bit-identical results.
+    fn simulate(&mut self, dt: f32) {
+        // Apply gravity
+        for body in &mut self.bodies {
+            if !body.is_static() {
+                body.acceleration = self.config.gravity;
             }
-            
-            // Integration (ASM accelerated)
-            integration::integrate_bodies(&mut self.bodies, dt);
-            
-            // Collision detection (ASM accelerated narrow phase)
-            self.contacts.clear();
-            collision::detect_collisions(&self.bodies, &mut self.contacts);
-            
-            // Resolve collisions
-            for _ in 0..self.config.iterations {
+        }
+
+        // Integration (ASM accelerated, or deterministic fixed-point).
+        integration::integrate_bodies_mode(&mut self.bodies, dt, self.config.integration);
+
+        // Broad phase: reduce the all-pairs scan to candidate pairs.
+        self.bounds.clear();
+        self.bounds.extend(
+            self.bodies
+                .iter()
+                .map(|b| AABB::from_center(b.position, b.shape.half_extents())),
+        );
+        let pairs = self.broad_phase.pairs(&self.bodies, &self.bounds);
+
+        // Collision detection (ASM accelerated narrow phase)
+        self.contacts.clear();
+        collision::detect_collisions_pairs(&self.bodies, &pairs, &mut self.contacts);
+
+        // Resolve collisions. The final iteration records a CollisionEvent per
+        // impacting pair so the settled impulse is reported to the game layer.
+        self.collision_events.clear();
+        let iterations = self.config.iterations.max(1);
+        for i in 0..iterations {
+            if i + 1 == iterations {
+                collision::resolve_contacts_events(
+                    &mut self.bodies,
+                    &self.contacts,
+                    &mut self.collision_events,
+                );
+            } else {
                 collision::resolve_contacts(&mut self.bodies, &self.contacts);
             }
         }
     }
-    
+
+    /// Take the collision events accumulated during the most recent step,
+    /// leaving the queue empty. Each event carries the impacting body indices,
+    /// the contact normal (A→B), and the resolved normal impulse magnitude.
+    pub fn drain_collision_events(&mut self) -> Vec<CollisionEvent> {
+        std::mem::take(&mut self.collision_events)
+    }
+
     pub fn body_count(&self) -> usize {
         self.bodies.len()
     }
+
+    /// The frame index that the next [`step`](Self::step) will produce.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Serialize every body (its `f32` fields, shape, and layer/mask) to a
+    /// little-endian byte buffer for rollback snapshots. The contact list is
+    /// transient and rebuilt each `step`, so it is not serialized.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.bodies.len() * 36);
+        bytes.extend_from_slice(&(self.bodies.len() as u32).to_le_bytes());
+        for body in &self.bodies {
+            for f in [
+                body.position.x,
+                body.position.y,
+                body.velocity.x,
+                body.velocity.y,
+                body.acceleration.x,
+                body.acceleration.y,
+                body.mass,
+                body.inv_mass,
+                body.restitution,
+                body.friction,
+            ] {
+                bytes.extend_from_slice(&f.to_le_bytes());
+            }
+            // Shape: tag byte + three f32 payload (x, y, angle). Circles store
+            // their radius in x; boxes store half-extents in x/y.
+            let (tag, sx, sy, angle) = match body.shape {
+                Shape::Circle { radius } => (0u8, radius, 0.0, 0.0),
+                Shape::Aabb { half_extents } => (1, half_extents.x, half_extents.y, 0.0),
+                Shape::Obb { half_extents, angle } => (2, half_extents.x, half_extents.y, angle),
+            };
+            bytes.push(tag);
+            bytes.extend_from_slice(&sx.to_le_bytes());
+            bytes.extend_from_slice(&sy.to_le_bytes());
+            bytes.extend_from_slice(&angle.to_le_bytes());
+            bytes.extend_from_slice(&body.layer.to_le_bytes());
+            bytes.extend_from_slice(&body.mask.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Restore bodies previously captured by [`snapshot`](Self::snapshot).
+    /// Returns `false` (leaving the world untouched) if `bytes` is truncated,
+    /// instead of panicking on a corrupt or foreign buffer.
+    pub fn restore(&mut self, bytes: &[u8]) -> bool {
+        let Some(bodies) = parse_bodies(bytes) else {
+            return false;
+        };
+        self.bodies = bodies;
+        self.contacts.clear();
+        true
+    }
+
+    /// Capture the world into a frame-keyed [`WorldSnapshot`] with a desync
+    /// checksum, ready to be stored in a [`WorldHistory`] ring.
+    pub fn save(&self) -> WorldSnapshot {
+        let bodies = self.snapshot();
+        let checksum = crate::rollback::checksum(&bodies);
+        WorldSnapshot {
+            frame: self.frame,
+            bodies,
+            checksum,
+        }
+    }
+
+    /// Restore a world previously captured by [`save`](Self::save), including
+    /// its frame index, so re-simulation resumes from the right step. Returns
+    /// `false` (leaving the world untouched) if the snapshot fails to parse.
+    pub fn load(&mut self, snapshot: &WorldSnapshot) -> bool {
+        if !self.restore(&snapshot.bodies) {
+            return false;
+        }
+        self.frame = snapshot.frame;
+        true
+    }
+}
+
+/// Bounds-checked parse of the body list [`PhysicsWorld::snapshot`] wrote,
+/// returning `None` on truncation rather than indexing past the end of a
+/// corrupt or foreign buffer.
+fn parse_bodies(bytes: &[u8]) -> Option<Vec<Body>> {
+    fn read_f32(bytes: &[u8], cursor: &mut usize) -> Option<f32> {
+        let v = f32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+        *cursor += 4;
+        Some(v)
+    }
+    fn read_u8(bytes: &[u8], cursor: &mut usize) -> Option<u8> {
+        let v = *bytes.get(*cursor)?;
+        *cursor += 1;
+        Some(v)
+    }
+    fn read_u32(bytes: &[u8], cursor: &mut usize) -> Option<u32> {
+        let v = u32::from_le_bytes(bytes.get(*cursor..*cursor + 4)?.try_into().ok()?);
+        *cursor += 4;
+        Some(v)
+    }
+
+    let mut cursor = 0;
+    let count = read_u32(bytes, &mut cursor)? as usize;
+    let mut bodies = Vec::with_capacity(count);
+    for _ in 0..count {
+        let position = Vec2::new(read_f32(bytes, &mut cursor)?, read_f32(bytes, &mut cursor)?);
+        let velocity = Vec2::new(read_f32(bytes, &mut cursor)?, read_f32(bytes, &mut cursor)?);
+        let acceleration = Vec2::new(read_f32(bytes, &mut cursor)?, read_f32(bytes, &mut cursor)?);
+        let mass = read_f32(bytes, &mut cursor)?;
+        let inv_mass = read_f32(bytes, &mut cursor)?;
+        let restitution = read_f32(bytes, &mut cursor)?;
+        let friction = read_f32(bytes, &mut cursor)?;
+        let tag = read_u8(bytes, &mut cursor)?;
+        let sx = read_f32(bytes, &mut cursor)?;
+        let sy = read_f32(bytes, &mut cursor)?;
+        let angle = read_f32(bytes, &mut cursor)?;
+        let layer = read_u32(bytes, &mut cursor)?;
+        let mask = read_u32(bytes, &mut cursor)?;
+        let shape = match tag {
+            0 => Shape::Circle { radius: sx },
+            1 => Shape::Aabb { half_extents: Vec2::new(sx, sy) },
+            _ => Shape::Obb { half_extents: Vec2::new(sx, sy), angle },
+        };
+        bodies.push(Body {
+            position,
+            velocity,
+            acceleration,
+            mass,
+            inv_mass,
+            restitution,
+            friction,
+            shape,
+            layer,
+            mask,
+        });
+    }
+    Some(bodies)
+}
+
+/// A physics snapshot keyed by frame index, carrying the serialized bodies and
+/// an FNV checksum for desync detection across peers.
+#[derive(Clone, Debug)]
+pub struct WorldSnapshot {
+    pub frame: u64,
+    pub bodies: Vec<u8>,
+    pub checksum: u64,
+}
+
+/// A ring buffer of the last `capacity` [`WorldSnapshot`]s. The rollback driver
+/// saves one per confirmed frame; on a mispredict it looks the frame up, calls
+/// [`PhysicsWorld::load`], and re-runs [`PhysicsWorld::step_dt`] to the present.
+pub struct WorldHistory {
+    frames: std::collections::VecDeque<WorldSnapshot>,
+    capacity: usize,
+}
+
+impl WorldHistory {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            frames: std::collections::VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Store a snapshot, evicting the oldest once `capacity` is reached.
+    pub fn push(&mut self, snapshot: WorldSnapshot) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(snapshot);
+    }
+
+    /// The retained snapshot for `frame`, if it is still in the window.
+    pub fn get(&self, frame: u64) -> Option<&WorldSnapshot> {
+        self.frames.iter().find(|s| s.frame == frame)
+    }
+
+    /// Drop every snapshot taken at or after `frame` (the frames about to be
+    /// re-simulated).
+    pub fn discard_from(&mut self, frame: u64) {
+        self.frames.retain(|s| s.frame < frame);
+    }
 }
 
 impl Default for PhysicsWorld {
@@ -145,6 +475,18 @@ impl Default for PhysicsWorld {
     }
 }
 
+/// A resolved collision surfaced to the game layer by
+/// [`PhysicsWorld::drain_collision_events`]. Indices refer into
+/// [`PhysicsWorld::bodies`]; `impulse` is the normal impulse magnitude, a proxy
+/// for impact strength that gameplay can threshold (e.g. to gate damage).
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionEvent {
+    pub a: usize,
+    pub b: usize,
+    pub normal: Vec2,
+    pub impulse: f32,
+}
+
 /// Contact information
 #[derive(Clone, Copy, Debug)]
 pub struct Contact {
@@ -0,0 +1,145 @@
+//! Delaunay triangulation
+//!
+//! Rust: Bowyer-Watson incremental triangulation for navmesh generation,
+//!       terrain, and fracturing static collider geometry.
+
+use crate::math::Vec2;
+
+/// Build a Delaunay triangulation of `points` with the incremental
+/// Bowyer-Watson algorithm.
+///
+/// Returns triangles as index triples into `points`, so callers can attach
+/// their own adjacency for pathfinding. Fewer than three points yield no
+/// triangles. The super-triangle introduced to bootstrap the insertion is
+/// discarded before returning, along with any triangle that still touches it.
+pub fn triangulate(points: &[Vec2]) -> Vec<[u32; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    // Build a super-triangle large enough to enclose every input point. Its
+    // three vertices are appended after the real points so they own the
+    // highest indices and are easy to strip at the end.
+    let mut min = points[0];
+    let mut max = points[0];
+    for p in points {
+        min.x = min.x.min(p.x);
+        min.y = min.y.min(p.y);
+        max.x = max.x.max(p.x);
+        max.y = max.y.max(p.y);
+    }
+    let dx = max.x - min.x;
+    let dy = max.y - min.y;
+    let delta = dx.max(dy).max(1.0) * 20.0;
+    let mid_x = (min.x + max.x) * 0.5;
+    let mid_y = (min.y + max.y) * 0.5;
+
+    let n = points.len() as u32;
+    let mut verts: Vec<Vec2> = points.to_vec();
+    verts.push(Vec2::new(mid_x - delta, mid_y - delta));
+    verts.push(Vec2::new(mid_x + delta, mid_y - delta));
+    verts.push(Vec2::new(mid_x, mid_y + delta));
+
+    // Current triangulation, seeded with the super-triangle.
+    let mut triangles: Vec<[u32; 3]> = vec![oriented_ccw(&verts, [n, n + 1, n + 2])];
+
+    // Scratch buffers reused across insertions.
+    let mut bad: Vec<usize> = Vec::new();
+    let mut boundary: Vec<[u32; 2]> = Vec::new();
+
+    for i in 0..n {
+        let p = verts[i as usize];
+
+        // Find every triangle whose circumcircle contains the new point.
+        bad.clear();
+        for (t, tri) in triangles.iter().enumerate() {
+            if in_circumcircle(&verts, *tri, p) {
+                bad.push(t);
+            }
+        }
+
+        // Collect the boundary of the polygonal cavity: edges of bad triangles
+        // that are not shared with another bad triangle.
+        boundary.clear();
+        for (bi, &t) in bad.iter().enumerate() {
+            let tri = triangles[t];
+            for e in 0..3 {
+                let edge = [tri[e], tri[(e + 1) % 3]];
+                let shared = bad.iter().enumerate().any(|(bj, &other)| {
+                    bi != bj && triangle_has_edge(triangles[other], edge)
+                });
+                if !shared {
+                    boundary.push(edge);
+                }
+            }
+        }
+
+        // Remove the bad triangles (high indices first so swap_remove is safe).
+        bad.sort_unstable_by(|a, b| b.cmp(a));
+        for &t in &bad {
+            triangles.swap_remove(t);
+        }
+
+        // Re-triangulate the cavity: connect the new point to each boundary edge.
+        for &[a, b] in &boundary {
+            triangles.push(oriented_ccw(&verts, [a, b, i]));
+        }
+    }
+
+    // Discard any triangle still referencing a super-triangle vertex.
+    triangles.retain(|tri| tri.iter().all(|&v| v < n));
+    triangles
+}
+
+/// Return the triangle with counter-clockwise winding, swapping two vertices if
+/// the signed area is negative. The in-circle test assumes CCW order.
+#[inline]
+fn oriented_ccw(verts: &[Vec2], tri: [u32; 3]) -> [u32; 3] {
+    let a = verts[tri[0] as usize];
+    let b = verts[tri[1] as usize];
+    let c = verts[tri[2] as usize];
+    if (b - a).cross(c - a) < 0.0 {
+        [tri[0], tri[2], tri[1]]
+    } else {
+        tri
+    }
+}
+
+/// Whether triangle `tri` has `edge` in either orientation.
+#[inline]
+fn triangle_has_edge(tri: [u32; 3], edge: [u32; 2]) -> bool {
+    for e in 0..3 {
+        let te = [tri[e], tri[(e + 1) % 3]];
+        if (te[0] == edge[0] && te[1] == edge[1]) || (te[0] == edge[1] && te[1] == edge[0]) {
+            return true;
+        }
+    }
+    false
+}
+
+/// In-circle test: point `d` lies inside the circumcircle of CCW triangle
+/// `tri` when the 3×3 determinant of the rows
+/// `[ax-dx, ay-dy, (ax-dx)² + (ay-dy)²]` (for A, B, C) is positive.
+#[inline]
+fn in_circumcircle(verts: &[Vec2], tri: [u32; 3], d: Vec2) -> bool {
+    let a = verts[tri[0] as usize];
+    let b = verts[tri[1] as usize];
+    let c = verts[tri[2] as usize];
+
+    let adx = a.x - d.x;
+    let ady = a.y - d.y;
+    let bdx = b.x - d.x;
+    let bdy = b.y - d.y;
+    let cdx = c.x - d.x;
+    let cdy = c.y - d.y;
+
+    let a_sq = adx * adx + ady * ady;
+    let b_sq = bdx * bdx + bdy * bdy;
+    let c_sq = cdx * cdx + cdy * cdy;
+
+    let det = adx * (bdy * c_sq - b_sq * cdy)
+        - ady * (bdx * c_sq - b_sq * cdx)
+        + a_sq * (bdx * cdy - bdy * cdx);
+
+    det > 0.0
+}
@@ -0,0 +1,122 @@
+//! Scrolling camera with world-bound clamping and render interpolation
+//!
+//! The camera decouples smooth visuals from the fixed simulation rate. It
+//! tracks a target world position, follows it smoothly, and clamps to the
+//! level bounds so it never scrolls past the edges (centering instead when the
+//! level is narrower than the viewport). Rendering passes
+//! [`FrameTick::interpolation`](crate::core::FrameTick) so the view is
+//! sub-pixel-interpolated between the previous and current fixed-update camera
+//! positions.
+
+use super::{software, Color, Renderer};
+use crate::math::Vec2;
+
+/// A 2D scrolling camera producing a world→screen offset.
+#[derive(Clone, Debug)]
+pub struct Camera {
+    /// Camera center this fixed update.
+    position: Vec2,
+    /// Camera center last fixed update (for render interpolation).
+    prev_position: Vec2,
+    /// Desired center the camera eases toward.
+    target: Vec2,
+    viewport: Vec2,
+    /// World-space level bounds `[min, max]`.
+    bounds_min: Vec2,
+    bounds_max: Vec2,
+    /// Follow smoothing in `[0, 1]`; 1.0 snaps, smaller lags.
+    smoothing: f32,
+}
+
+impl Camera {
+    pub fn new(viewport_w: u32, viewport_h: u32) -> Self {
+        let viewport = Vec2::new(viewport_w as f32, viewport_h as f32);
+        Self {
+            position: Vec2::ZERO,
+            prev_position: Vec2::ZERO,
+            target: Vec2::ZERO,
+            viewport,
+            bounds_min: Vec2::ZERO,
+            bounds_max: viewport,
+            smoothing: 0.15,
+        }
+    }
+
+    /// Set the world-space level bounds the camera clamps against.
+    pub fn set_bounds(&mut self, min: Vec2, max: Vec2) {
+        self.bounds_min = min;
+        self.bounds_max = max;
+    }
+
+    pub fn set_smoothing(&mut self, smoothing: f32) {
+        self.smoothing = smoothing.clamp(0.0, 1.0);
+    }
+
+    /// Point the camera at `target` (typically the player position).
+    pub fn follow(&mut self, target: Vec2) {
+        self.target = target;
+    }
+
+    /// Advance the camera one fixed update: store the previous position and ease
+    /// toward the target, then clamp to bounds.
+    pub fn fixed_update(&mut self) {
+        self.prev_position = self.position;
+        self.position = self.position.lerp(self.target, self.smoothing);
+        self.position = self.clamp_center(self.position);
+    }
+
+    /// Clamp a center so the viewport stays inside the level bounds, centering
+    /// on any axis where the level is narrower than the viewport.
+    fn clamp_center(&self, center: Vec2) -> Vec2 {
+        let half = self.viewport * 0.5;
+        let level = self.bounds_max - self.bounds_min;
+        let x = if level.x <= self.viewport.x {
+            (self.bounds_min.x + self.bounds_max.x) * 0.5
+        } else {
+            center.x.clamp(self.bounds_min.x + half.x, self.bounds_max.x - half.x)
+        };
+        let y = if level.y <= self.viewport.y {
+            (self.bounds_min.y + self.bounds_max.y) * 0.5
+        } else {
+            center.y.clamp(self.bounds_min.y + half.y, self.bounds_max.y - half.y)
+        };
+        Vec2::new(x, y)
+    }
+
+    /// Interpolated camera center for rendering at sub-step `interpolation`.
+    fn render_center(&self, interpolation: f32) -> Vec2 {
+        self.clamp_center(self.prev_position.lerp(self.position, interpolation))
+    }
+
+    /// Top-left world position of the viewport for this render frame.
+    #[inline]
+    fn render_origin(&self, interpolation: f32) -> Vec2 {
+        self.render_center(interpolation) - self.viewport * 0.5
+    }
+
+    /// Convert a world position to screen space for this render frame.
+    #[inline]
+    pub fn world_to_screen(&self, world: Vec2, interpolation: f32) -> Vec2 {
+        world - self.render_origin(interpolation)
+    }
+
+    /// Convert a screen position back to world space for this render frame.
+    #[inline]
+    pub fn screen_to_world(&self, screen: Vec2, interpolation: f32) -> Vec2 {
+        screen + self.render_origin(interpolation)
+    }
+
+    /// Camera-relative horizontal line (see [`software::hline`]).
+    pub fn hline(&self, renderer: &mut Renderer, x0: f32, x1: f32, y: f32, interp: f32, color: Color) {
+        let a = self.world_to_screen(Vec2::new(x0, y), interp);
+        let b = self.world_to_screen(Vec2::new(x1, y), interp);
+        software::hline(renderer, a.x as i32, b.x as i32, a.y as i32, color);
+    }
+
+    /// Camera-relative vertical line (see [`software::vline`]).
+    pub fn vline(&self, renderer: &mut Renderer, x: f32, y0: f32, y1: f32, interp: f32, color: Color) {
+        let a = self.world_to_screen(Vec2::new(x, y0), interp);
+        let b = self.world_to_screen(Vec2::new(x, y1), interp);
+        software::vline(renderer, a.x as i32, a.y as i32, b.y as i32, color);
+    }
+}
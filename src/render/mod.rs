@@ -5,6 +5,12 @@
 
 pub mod software;
 pub mod raycast;
+pub mod particles;
+pub mod camera;
+#[cfg(feature = "imgui")]
+pub mod overlay;
+
+pub use camera::Camera;
 
 use crate::math::Vec2;
 
@@ -53,6 +59,15 @@ impl Renderer {
         }
     }
     
+    /// Set a pixel, alpha-blending the source over the existing buffer value
+    #[inline]
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
+            let idx = (y as u32 * self.width + x as u32) as usize;
+            self.buffer[idx] = software::blend_alpha(self.buffer[idx], color);
+        }
+    }
+
     /// Get a pixel
     #[inline]
     pub fn get_pixel(&self, x: i32, y: i32) -> Color {
@@ -114,6 +129,47 @@ impl Renderer {
         }
     }
     
+    /// Draw a thick line as a filled quad oriented along the start→end direction.
+    ///
+    /// `half_width` is the perpendicular extent on each side of the line; when
+    /// `blend` is set the quad is composited over the existing buffer using the
+    /// color's alpha channel, otherwise pixels are overwritten.
+    pub fn draw_thick_line(&mut self, start: Vec2, end: Vec2, half_width: f32, color: Color, blend: bool) {
+        let dir = end - start;
+        let len = dir.length();
+        if len <= 0.0 || half_width <= 0.0 { return; }
+        // Unit perpendicular to the line gives the quad's half-width offset.
+        let perp = Vec2::new(-dir.y / len, dir.x / len) * half_width;
+        let quad = [
+            start + perp,
+            end + perp,
+            end - perp,
+            start - perp,
+        ];
+        software::fill_quad(self, &quad, color, blend);
+    }
+
+    /// Draw a distance-scaled tracer/beam between two world points.
+    ///
+    /// The drawn width is `base_width * sqrt(clamp(dist_sq, min, max) / max)`, so
+    /// the width grows with `dist_sq` and reaches the full `base_width` at
+    /// `max_dist_sq`. The segment is
+    /// clamped to `params.max_len` measured from `start` toward `end`.
+    pub fn draw_tracer(&mut self, start: Vec2, end: Vec2, base_width: f32, color: Color, dist_sq: f32, params: TracerParams) {
+        let scale = (dist_sq.clamp(params.min_dist_sq, params.max_dist_sq) / params.max_dist_sq).sqrt();
+        let half_width = (base_width * scale * 0.5).max(0.5);
+
+        let dir = end - start;
+        let len = dir.length();
+        let drawn_end = if len > params.max_len && len > 0.0 {
+            start + dir * (params.max_len / len)
+        } else {
+            end
+        };
+
+        self.draw_thick_line(start, drawn_end, half_width, color, params.blend);
+    }
+
     /// Draw text (simple 8x8 font placeholder)
     pub fn draw_text(&mut self, _text: &str, _x: i32, _y: i32, _color: Color) {
         // TODO: Implement bitmap font rendering
@@ -130,6 +186,30 @@ impl Renderer {
     }
 }
 
+/// Tuning for [`Renderer::draw_tracer`].
+#[derive(Clone, Copy, Debug)]
+pub struct TracerParams {
+    /// Distance-squared at which the tracer reaches its minimum width.
+    pub min_dist_sq: f32,
+    /// Distance-squared at which the tracer reaches its full `base_width`.
+    pub max_dist_sq: f32,
+    /// Maximum drawn length measured from `start`.
+    pub max_len: f32,
+    /// Alpha-blend the quad against the buffer instead of overwriting.
+    pub blend: bool,
+}
+
+impl Default for TracerParams {
+    fn default() -> Self {
+        Self {
+            min_dist_sq: 1.0,
+            max_dist_sq: 1.0,
+            max_len: f32::INFINITY,
+            blend: true,
+        }
+    }
+}
+
 /// Draw command for batching
 #[derive(Clone, Debug)]
 pub enum DrawCommand {
@@ -138,6 +218,7 @@ pub enum DrawCommand {
     Rect { x: i32, y: i32, w: u32, h: u32, color: Color },
     Circle { x: i32, y: i32, radius: i32, color: Color },
     Line { x0: i32, y0: i32, x1: i32, y1: i32, color: Color },
+    Tracer { start: Vec2, end: Vec2, base_width: f32, color: Color, dist_sq: f32, params: TracerParams },
 }
 
 /// Command buffer for deferred rendering
@@ -161,6 +242,10 @@ impl CommandBuffer {
     pub fn circle(&mut self, x: i32, y: i32, radius: i32, color: Color) {
         self.commands.push(DrawCommand::Circle { x, y, radius, color });
     }
+
+    pub fn tracer(&mut self, start: Vec2, end: Vec2, base_width: f32, color: Color, dist_sq: f32, params: TracerParams) {
+        self.commands.push(DrawCommand::Tracer { start, end, base_width, color, dist_sq, params });
+    }
     
     pub fn execute(&self, renderer: &mut Renderer) {
         for cmd in &self.commands {
@@ -170,6 +255,8 @@ impl CommandBuffer {
                 DrawCommand::Rect { x, y, w, h, color } => renderer.fill_rect(*x, *y, *w, *h, *color),
                 DrawCommand::Circle { x, y, radius, color } => renderer.fill_circle(*x, *y, *radius, *color),
                 DrawCommand::Line { x0, y0, x1, y1, color } => renderer.draw_line(*x0, *y0, *x1, *y1, *color),
+                DrawCommand::Tracer { start, end, base_width, color, dist_sq, params } =>
+                    renderer.draw_tracer(*start, *end, *base_width, *color, *dist_sq, *params),
             }
         }
     }
@@ -0,0 +1,200 @@
+//! ImGui dev overlay (behind the `imgui` feature)
+//!
+//! A live tuning/profiling panel drawn on top of the software framebuffer.
+//! Because the renderer targets a CPU `&mut [u32]` buffer rather than a GPU
+//! surface, imgui's draw data is rasterized here by a small software draw-list
+//! renderer that blends each triangle's vertex color into the buffer.
+//!
+//! The rasterizer fills triangles with interpolated vertex colors; it does not
+//! sample the font atlas, so glyphs render as their solid quad color — enough
+//! for a readable debug HUD without a texture-sampling path. Panels, sliders,
+//! and backgrounds render fully.
+
+use imgui::{Context, DrawCmd, DrawData};
+use imgui_winit_support::{HiDpiMode, WinitPlatform};
+use winit::event::WindowEvent;
+use winit::window::Window;
+
+/// Values the overlay edits live, shared with the game each frame.
+#[derive(Clone, Copy, Debug)]
+pub struct Tunables {
+    pub move_speed: f32,
+    pub rot_speed: f32,
+    pub fov: f32,
+    pub ceiling: u32,
+    pub floor: u32,
+}
+
+impl Default for Tunables {
+    fn default() -> Self {
+        Self { move_speed: 3.0, rot_speed: 2.0, fov: 0.66, ceiling: 0x00404060, floor: 0x00505050 }
+    }
+}
+
+/// Read-only stats the overlay displays.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stats {
+    pub fps: f32,
+    pub frame_ms: f32,
+    pub pos: (f32, f32),
+    pub dir: (f32, f32),
+}
+
+/// Holds the imgui context + winit platform and draws the debug panel.
+pub struct DevOverlay {
+    ctx: Context,
+    platform: WinitPlatform,
+    pub visible: bool,
+}
+
+impl DevOverlay {
+    pub fn new(window: &Window) -> Self {
+        let mut ctx = Context::create();
+        ctx.set_ini_filename(None);
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.attach_window(ctx.io_mut(), window, HiDpiMode::Default);
+        Self { ctx, platform, visible: false }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Forward a window event to imgui so it tracks mouse/keyboard.
+    pub fn handle_event(&mut self, window: &Window, event: &WindowEvent) {
+        self.platform.handle_window_event(self.ctx.io_mut(), window, event);
+    }
+
+    /// Build the UI for this frame and rasterize it into `buffer`. Mutates
+    /// `tunables` in place through the sliders. No-op while hidden.
+    pub fn render(
+        &mut self,
+        window: &Window,
+        buffer: &mut [u32],
+        width: u32,
+        height: u32,
+        stats: &Stats,
+        tunables: &mut Tunables,
+    ) {
+        if !self.visible {
+            return;
+        }
+        self.platform
+            .prepare_frame(self.ctx.io_mut(), window)
+            .expect("imgui prepare_frame");
+        let ui = self.ctx.new_frame();
+
+        ui.window("Engine")
+            .size([240.0, 220.0], imgui::Condition::FirstUseEver)
+            .build(|| {
+                ui.text(format!("{:.0} fps ({:.2} ms)", stats.fps, stats.frame_ms));
+                ui.text(format!("pos ({:.2}, {:.2})", stats.pos.0, stats.pos.1));
+                ui.text(format!("dir ({:.2}, {:.2})", stats.dir.0, stats.dir.1));
+                ui.separator();
+                ui.slider("move speed", 0.5, 8.0, &mut tunables.move_speed);
+                ui.slider("rot speed", 0.5, 6.0, &mut tunables.rot_speed);
+                ui.slider("fov", 0.3, 1.5, &mut tunables.fov);
+                edit_color(ui, "ceiling", &mut tunables.ceiling);
+                edit_color(ui, "floor", &mut tunables.floor);
+            });
+
+        let draw_data = self.ctx.render();
+        rasterize(draw_data, buffer, width, height);
+    }
+}
+
+/// Expose an ARGB u32 as an imgui RGB color editor.
+fn edit_color(ui: &imgui::Ui, label: &str, color: &mut u32) {
+    let mut rgb = [
+        ((*color >> 16) & 0xFF) as f32 / 255.0,
+        ((*color >> 8) & 0xFF) as f32 / 255.0,
+        (*color & 0xFF) as f32 / 255.0,
+    ];
+    if ui.color_edit3(label, &mut rgb) {
+        let r = (rgb[0] * 255.0) as u32;
+        let g = (rgb[1] * 255.0) as u32;
+        let b = (rgb[2] * 255.0) as u32;
+        *color = (r << 16) | (g << 8) | b;
+    }
+}
+
+/// Blend imgui draw data into the CPU buffer by filling each triangle with its
+/// interpolated vertex color (alpha-blended over the existing pixels).
+fn rasterize(draw_data: &DrawData, buffer: &mut [u32], width: u32, height: u32) {
+    for list in draw_data.draw_lists() {
+        let vtx = list.vtx_buffer();
+        let idx = list.idx_buffer();
+        for cmd in list.commands() {
+            let DrawCmd::Elements { count, cmd_params } = cmd else {
+                continue;
+            };
+            let start = cmd_params.idx_offset;
+            let [cx, cy, cz, cw] = cmd_params.clip_rect;
+            for tri in 0..count / 3 {
+                let i0 = idx[start + tri * 3] as usize + cmd_params.vtx_offset;
+                let i1 = idx[start + tri * 3 + 1] as usize + cmd_params.vtx_offset;
+                let i2 = idx[start + tri * 3 + 2] as usize + cmd_params.vtx_offset;
+                fill_triangle(
+                    &vtx[i0], &vtx[i1], &vtx[i2],
+                    (cx, cy, cz, cw), buffer, width, height,
+                );
+            }
+        }
+    }
+}
+
+fn fill_triangle(
+    a: &imgui::DrawVert,
+    b: &imgui::DrawVert,
+    c: &imgui::DrawVert,
+    clip: (f32, f32, f32, f32),
+    buffer: &mut [u32],
+    width: u32,
+    height: u32,
+) {
+    let (x0, y0) = (a.pos[0], a.pos[1]);
+    let (x1, y1) = (b.pos[0], b.pos[1]);
+    let (x2, y2) = (c.pos[0], c.pos[1]);
+
+    let min_x = x0.min(x1).min(x2).max(clip.0).max(0.0) as i32;
+    let max_x = x0.max(x1).max(x2).min(clip.2).min(width as f32 - 1.0) as i32;
+    let min_y = y0.min(y1).min(y2).max(clip.1).max(0.0) as i32;
+    let max_y = y0.max(y1).max(y2).min(clip.3).min(height as f32 - 1.0) as i32;
+
+    let area = edge(x0, y0, x1, y1, x2, y2);
+    if area.abs() < f32::EPSILON {
+        return;
+    }
+
+    for py in min_y..=max_y {
+        for px in min_x..=max_x {
+            let (fx, fy) = (px as f32 + 0.5, py as f32 + 0.5);
+            let w0 = edge(x1, y1, x2, y2, fx, fy) / area;
+            let w1 = edge(x2, y2, x0, y0, fx, fy) / area;
+            let w2 = edge(x0, y0, x1, y1, fx, fy) / area;
+            if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                continue;
+            }
+            // Interpolate vertex color (RGBA8).
+            let r = w0 * a.col[0] as f32 + w1 * b.col[0] as f32 + w2 * c.col[0] as f32;
+            let g = w0 * a.col[1] as f32 + w1 * b.col[1] as f32 + w2 * c.col[1] as f32;
+            let bl = w0 * a.col[2] as f32 + w1 * b.col[2] as f32 + w2 * c.col[2] as f32;
+            let al = (w0 * a.col[3] as f32 + w1 * b.col[3] as f32 + w2 * c.col[3] as f32) / 255.0;
+
+            let idx = py as usize * width as usize + px as usize;
+            let dst = buffer[idx];
+            let dr = ((dst >> 16) & 0xFF) as f32;
+            let dg = ((dst >> 8) & 0xFF) as f32;
+            let db = (dst & 0xFF) as f32;
+            let or = (r * al + dr * (1.0 - al)) as u32;
+            let og = (g * al + dg * (1.0 - al)) as u32;
+            let ob = (bl * al + db * (1.0 - al)) as u32;
+            buffer[idx] = (or << 16) | (og << 8) | ob;
+        }
+    }
+}
+
+#[inline]
+fn edge(ax: f32, ay: f32, bx: f32, by: f32, px: f32, py: f32) -> f32 {
+    (px - ax) * (by - ay) - (py - ay) * (bx - ax)
+}
@@ -0,0 +1,394 @@
+//! Particle / caret effects
+//!
+//! A lightweight pool for transient visual effects (hit sparks, dust,
+//! explosions, muzzle flashes, projectile trails, engine flares) living
+//! alongside the software [`Renderer`]. Particle state is stored in
+//! structure-of-arrays [`Vec2Array`]s for positions and velocities so the whole
+//! pool integrates with two SIMD batch passes ([`simd::vec2_add_batch`] and
+//! [`simd::vec2_mul_scalar_batch`]) — thousands of particles advance without
+//! per-particle allocation or per-particle ECS rows.
+//!
+//! Everything advances by fixed-tick counts and any randomness (spread angle,
+//! lifetime jitter) is drawn from a seeded integer RNG threaded through the
+//! loop — never `rand::thread_rng` — so effects are deterministic and survive
+//! rollback re-simulation.
+//!
+//! [`simd::vec2_add_batch`]: crate::math::simd::vec2_add_batch
+//! [`simd::vec2_mul_scalar_batch`]: crate::math::simd::vec2_mul_scalar_batch
+
+use super::{software::blend_alpha, Color, Renderer};
+use crate::math::simd::{self, Vec2Array};
+use crate::math::Vec2;
+
+/// Seeded 32-bit xorshift used for spread/jitter. Deterministic given a seed.
+#[derive(Clone, Copy, Debug)]
+pub struct ParticleRng {
+    state: u32,
+}
+
+impl ParticleRng {
+    pub fn new(seed: u32) -> Self {
+        // xorshift requires a non-zero state.
+        Self { state: seed | 1 }
+    }
+
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Uniform value in `[-1.0, 1.0]`.
+    #[inline]
+    fn next_signed(&mut self) -> f32 {
+        (self.next_u32() as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    /// Uniform value in `[0.0, 1.0]`.
+    #[inline]
+    fn next_unit(&mut self) -> f32 {
+        self.next_u32() as f32 / u32::MAX as f32
+    }
+}
+
+/// One animation frame: a sprite rectangle and the color blitted for it.
+#[derive(Clone, Copy, Debug)]
+pub struct ParticleFrame {
+    pub width: u32,
+    pub height: u32,
+    pub color: Color,
+}
+
+/// Template describing a frame-animated effect spawned with [`ParticleSystem::spawn`].
+#[derive(Clone, Debug)]
+pub struct ParticleTemplate {
+    /// Sprite rectangles played in order.
+    pub frames: Vec<ParticleFrame>,
+    /// Extra fixed ticks each frame is held for: a frame stays up for
+    /// `frame_ticks + 1` updates (0 advances every update).
+    pub frame_ticks: u16,
+    /// Base lifetime in fixed ticks.
+    pub lifetime: u16,
+    /// Random lifetime jitter, `±jitter` ticks.
+    pub lifetime_jitter: u16,
+    /// Launch speed in units/tick applied along the spawn direction.
+    pub speed: f32,
+    /// Random spread applied to the launch direction, in radians.
+    pub spread: f32,
+}
+
+/// How an [`Emitter`] releases particles when ticked by
+/// [`ParticleSystem::emit`].
+#[derive(Clone, Copy, Debug)]
+pub enum EmitMode {
+    /// Release `count` particles in a single call (explosions, impacts).
+    Burst { count: u32 },
+    /// Release `rate * dt` particles per call, rounded to the nearest whole
+    /// particle (trails, engine flares). The fractional remainder is not
+    /// carried between calls, so a `rate * dt` below 0.5 releases nothing.
+    PerSecond { rate: f32 },
+}
+
+/// Data-defined emitter: the shape of one kind of effect. Mirrors the effect
+/// definitions used for explosions and projectile expiry — color ramp, size,
+/// jittered lifetime, and how much of the emitting body's velocity to inherit.
+#[derive(Clone, Copy, Debug)]
+pub struct Emitter {
+    /// Color at spawn, lerped toward [`end_color`](Self::end_color) over life.
+    pub start_color: Color,
+    /// Color at death.
+    pub end_color: Color,
+    /// Square particle size in pixels.
+    pub size: u32,
+    /// Base lifetime in fixed ticks.
+    pub lifetime: u16,
+    /// Random lifetime jitter, `±jitter` ticks.
+    pub lifetime_jitter: u16,
+    /// Launch speed in units/tick applied along a random direction.
+    pub speed: f32,
+    /// Fraction of the emitting body's velocity copied onto each particle.
+    pub velocity_inherit: f32,
+    /// Emission schedule.
+    pub mode: EmitMode,
+}
+
+impl Default for Emitter {
+    fn default() -> Self {
+        Self {
+            start_color: 0xFFFFFFFF,
+            end_color: 0x00FFFFFF,
+            size: 2,
+            lifetime: 30,
+            lifetime_jitter: 0,
+            speed: 1.0,
+            velocity_inherit: 0.0,
+            mode: EmitMode::Burst { count: 16 },
+        }
+    }
+}
+
+/// SoA pool of live particles.
+pub struct ParticleSystem {
+    templates: Vec<ParticleTemplate>,
+    positions: Vec2Array,
+    velocities: Vec2Array,
+    /// Current animation-frame index (frame-animated particles only).
+    frame: Vec<u16>,
+    /// Ticks remaining before the frame advances.
+    frame_timer: Vec<u16>,
+    /// Ticks remaining before despawn.
+    life: Vec<u16>,
+    /// Lifetime at spawn, for normalized-age color fade.
+    max_life: Vec<u16>,
+    /// Template index, or `u16::MAX` for a color-ramp (emitter) particle.
+    template: Vec<u16>,
+    /// Start/end colors and size for color-ramp particles.
+    start_color: Vec<Color>,
+    end_color: Vec<Color>,
+    size: Vec<u32>,
+    /// Global per-tick velocity damping applied by the batch velocity pass.
+    damping: f32,
+    /// Scratch buffer reused by the SIMD integration passes.
+    scratch: Vec<Vec2>,
+}
+
+/// Sentinel [`template`](ParticleSystem::template) for color-ramp particles.
+const NO_TEMPLATE: u16 = u16::MAX;
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        Self {
+            templates: Vec::new(),
+            positions: Vec2Array::new(0),
+            velocities: Vec2Array::new(0),
+            frame: Vec::new(),
+            frame_timer: Vec::new(),
+            life: Vec::new(),
+            max_life: Vec::new(),
+            template: Vec::new(),
+            start_color: Vec::new(),
+            end_color: Vec::new(),
+            size: Vec::new(),
+            damping: 1.0,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Set the global per-tick velocity damping (1.0 = none).
+    pub fn set_damping(&mut self, damping: f32) {
+        self.damping = damping;
+    }
+
+    /// Register a frame-animated template, returning its id.
+    pub fn register(&mut self, template: ParticleTemplate) -> u16 {
+        let id = self.templates.len() as u16;
+        self.templates.push(template);
+        id
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Spawn a frame-animated particle of `template` at `pos` travelling along
+    /// `dir`.
+    ///
+    /// Spread and lifetime jitter are drawn from `rng` so the spawn is
+    /// reproducible for a given RNG stream.
+    pub fn spawn(&mut self, template: u16, pos: Vec2, dir: Vec2, rng: &mut ParticleRng) {
+        let Some(t) = self.templates.get(template as usize) else {
+            return;
+        };
+
+        let angle = dir.angle() + rng.next_signed() * t.spread;
+        let speed = t.speed;
+        let jitter = (rng.next_signed() * t.lifetime_jitter as f32) as i32;
+        let life = (t.lifetime as i32 + jitter).max(1) as u16;
+
+        self.push_particle(
+            pos,
+            Vec2::new(angle.cos() * speed, angle.sin() * speed),
+            life,
+            template,
+            0,
+            0,
+            0,
+        );
+    }
+
+    /// Release particles from a data-defined `emitter` at `origin`, inheriting a
+    /// fraction of `inherited_velocity` (e.g. the emitting body's velocity).
+    ///
+    /// `dt` is the fixed timestep in seconds, used by [`EmitMode::PerSecond`] to
+    /// turn its rate into a rounded per-call count; it is ignored for bursts. Directions and
+    /// lifetime jitter are drawn from `rng`.
+    pub fn emit(
+        &mut self,
+        emitter: &Emitter,
+        origin: Vec2,
+        inherited_velocity: Vec2,
+        dt: f32,
+        rng: &mut ParticleRng,
+    ) {
+        let count = match emitter.mode {
+            EmitMode::Burst { count } => count,
+            EmitMode::PerSecond { rate } => (rate * dt).round() as u32,
+        };
+
+        let inherited = inherited_velocity * emitter.velocity_inherit;
+        for _ in 0..count {
+            let angle = rng.next_unit() * std::f32::consts::TAU;
+            let vel = Vec2::new(angle.cos() * emitter.speed, angle.sin() * emitter.speed) + inherited;
+            let jitter = (rng.next_signed() * emitter.lifetime_jitter as f32) as i32;
+            let life = (emitter.lifetime as i32 + jitter).max(1) as u16;
+            self.push_particle(
+                origin,
+                vel,
+                life,
+                NO_TEMPLATE,
+                emitter.start_color,
+                emitter.end_color,
+                emitter.size,
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_particle(
+        &mut self,
+        pos: Vec2,
+        vel: Vec2,
+        life: u16,
+        template: u16,
+        start_color: Color,
+        end_color: Color,
+        size: u32,
+    ) {
+        self.positions.push(pos);
+        self.velocities.push(vel);
+        self.frame.push(0);
+        self.frame_timer.push(
+            self.templates
+                .get(template as usize)
+                .map_or(0, |t| t.frame_ticks),
+        );
+        self.life.push(life);
+        self.max_life.push(life);
+        self.template.push(template);
+        self.start_color.push(start_color);
+        self.end_color.push(end_color);
+        self.size.push(size);
+    }
+
+    /// Advance every live particle one fixed update: one batch velocity
+    /// integration (global damping) and one batch position integration, then
+    /// retire the expired via swap-remove.
+    pub fn fixed_update(&mut self) {
+        let n = self.positions.len();
+        if n == 0 {
+            return;
+        }
+
+        self.scratch.resize(n, Vec2::ZERO);
+
+        // Batch velocity integration: v *= damping (global), via the SIMD
+        // scalar-multiply pass.
+        simd::vec2_mul_scalar_batch(&self.velocities.data, self.damping, &mut self.scratch);
+        self.velocities.data.copy_from_slice(&self.scratch);
+
+        // Batch position integration: p += v.
+        simd::vec2_add_batch(&self.positions.data, &self.velocities.data, &mut self.scratch);
+        self.positions.data.copy_from_slice(&self.scratch);
+
+        let mut i = 0;
+        while i < self.positions.len() {
+            // Advance the animation frame for templated particles.
+            if self.template[i] != NO_TEMPLATE {
+                if self.frame_timer[i] == 0 {
+                    let tpl = &self.templates[self.template[i] as usize];
+                    let last = tpl.frames.len().saturating_sub(1) as u16;
+                    self.frame[i] = (self.frame[i] + 1).min(last);
+                    self.frame_timer[i] = tpl.frame_ticks;
+                } else {
+                    self.frame_timer[i] -= 1;
+                }
+            }
+
+            if self.life[i] <= 1 {
+                self.swap_remove(i);
+                // Do not advance `i`: a swapped-in particle now occupies it.
+                continue;
+            }
+            self.life[i] -= 1;
+            i += 1;
+        }
+    }
+
+    fn swap_remove(&mut self, i: usize) {
+        self.positions.data.swap_remove(i);
+        self.velocities.data.swap_remove(i);
+        self.frame.swap_remove(i);
+        self.frame_timer.swap_remove(i);
+        self.life.swap_remove(i);
+        self.max_life.swap_remove(i);
+        self.template.swap_remove(i);
+        self.start_color.swap_remove(i);
+        self.end_color.swap_remove(i);
+        self.size.swap_remove(i);
+    }
+
+    /// Normalized age in `[0.0, 1.0]`: 0 at spawn, 1 at death.
+    #[inline]
+    fn age(&self, i: usize) -> f32 {
+        let max = self.max_life[i].max(1) as f32;
+        1.0 - (self.life[i] as f32 / max)
+    }
+
+    /// Blit every live particle into the renderer, alpha-blended over the
+    /// existing buffer. Templated particles play their current frame; emitter
+    /// particles fade from start to end color by normalized age.
+    pub fn render(&self, renderer: &mut Renderer) {
+        for i in 0..self.positions.len() {
+            let (w, h, color) = if self.template[i] == NO_TEMPLATE {
+                let age = self.age(i);
+                let color = super::software::lerp_color(self.start_color[i], self.end_color[i], age);
+                // Fade alpha out linearly over the particle's life.
+                let alpha = (255.0 * (1.0 - age)).clamp(0.0, 255.0) as u32;
+                let faded = (color & 0x00FF_FFFF) | (alpha << 24);
+                (self.size[i], self.size[i], faded)
+            } else {
+                let tpl = &self.templates[self.template[i] as usize];
+                let Some(frame) = tpl.frames.get(self.frame[i] as usize) else {
+                    continue;
+                };
+                (frame.width, frame.height, frame.color)
+            };
+
+            let p = self.positions.data[i];
+            let x0 = p.x as i32 - w as i32 / 2;
+            let y0 = p.y as i32 - h as i32 / 2;
+            for dy in 0..h as i32 {
+                for dx in 0..w as i32 {
+                    let x = x0 + dx;
+                    let y = y0 + dy;
+                    let dst = renderer.get_pixel(x, y);
+                    renderer.set_pixel(x, y, blend_alpha(dst, color));
+                }
+            }
+        }
+    }
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,9 +3,34 @@
 //! Rust: Ray setup, wall/floor logic
 //! ASM: Inner raycast loop (DDA algorithm)
 
-use crate::math::Vec2;
+use std::collections::HashMap;
+use crate::math::{FixedPoint, FixedVec2, Vec2};
 use super::{Renderer, Color, colors};
 
+/// A wall/floor texture as a row-major ARGB pixel grid.
+#[derive(Clone, Debug)]
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u32>,
+}
+
+impl Texture {
+    /// Sample a texel, clamping the coordinates to the texture bounds.
+    #[inline]
+    fn sample(&self, tx: u32, ty: u32) -> u32 {
+        let x = tx.min(self.width.saturating_sub(1));
+        let y = ty.min(self.height.saturating_sub(1));
+        self.pixels[(y * self.width + x) as usize]
+    }
+}
+
+/// Halve each RGB channel (keeping alpha) to shade y-side walls.
+#[inline]
+fn darken(color: u32) -> u32 {
+    (color & 0xFF00_0000) | ((color >> 1) & 0x007F_7F7F)
+}
+
 #[cfg(not(no_asm))]
 extern "C" {
     fn raycast_dda_batch(
@@ -43,8 +68,22 @@ pub struct Raycaster {
     pub position: Vec2,
     pub direction: Vec2,
     pub plane: Vec2,
+    /// Enable textured walls and floor/ceiling casting; when `false` (or when a
+    /// cell has no loaded texture) the old flat-shaded path is used.
+    pub textured: bool,
+    /// Route ray stepping through the 16.16 fixed-point DDA so a given camera
+    /// pose produces a bit-identical frame on any CPU.
+    pub deterministic: bool,
     distances: Vec<f32>,
     hit_sides: Vec<u8>,
+    /// Map cell value hit by each column's ray, used to pick the wall texture.
+    hit_cells: Vec<u8>,
+    /// Fractional hit position along the wall for each column, in `[0, 1)`.
+    wall_xs: Vec<f32>,
+    /// Wall textures keyed by the nonzero map cell value.
+    textures: HashMap<u8, Texture>,
+    floor_texture: Option<Texture>,
+    ceiling_texture: Option<Texture>,
 }
 
 impl Raycaster {
@@ -55,10 +94,32 @@ impl Raycaster {
             position: Vec2::new(2.0, 2.0),
             direction: Vec2::new(1.0, 0.0),
             plane: Vec2::new(0.0, plane_length),
+            textured: true,
+            deterministic: false,
             distances: vec![0.0; width as usize],
             hit_sides: vec![0; width as usize],
+            hit_cells: vec![0; width as usize],
+            wall_xs: vec![0.0; width as usize],
+            textures: HashMap::new(),
+            floor_texture: None,
+            ceiling_texture: None,
         }
     }
+
+    /// Register a wall texture for the given nonzero map cell value.
+    pub fn load_texture(&mut self, cell_value: u8, width: u32, height: u32, pixels: Vec<u32>) {
+        self.textures.insert(cell_value, Texture { width, height, pixels });
+    }
+
+    /// Set the texture cast onto the floor (below the horizon).
+    pub fn set_floor_texture(&mut self, width: u32, height: u32, pixels: Vec<u32>) {
+        self.floor_texture = Some(Texture { width, height, pixels });
+    }
+
+    /// Set the texture cast onto the ceiling (above the horizon).
+    pub fn set_ceiling_texture(&mut self, width: u32, height: u32, pixels: Vec<u32>) {
+        self.ceiling_texture = Some(Texture { width, height, pixels });
+    }
     
     /// Rotate the camera
     pub fn rotate(&mut self, angle: f32) {
@@ -106,8 +167,9 @@ impl Raycaster {
     pub fn render(&mut self, renderer: &mut Renderer, map: &[u8], map_width: u32, map_height: u32) {
         let width = renderer.width;
         let height = renderer.height;
-        
-        // Clear with ceiling and floor
+
+        // Clear with flat ceiling/floor bands (also the fallback when texturing
+        // is off or no floor/ceiling texture is loaded).
         for y in 0..height / 2 {
             for x in 0..width {
                 renderer.buffer[(y * width + x) as usize] = 0xFF333333; // Ceiling
@@ -118,34 +180,97 @@ impl Raycaster {
                 renderer.buffer[(y * width + x) as usize] = 0xFF666666; // Floor
             }
         }
-        
-        // Cast rays
-        self.cast_rays(map, map_width, map_height, width);
-        
-        // Draw walls
+
+        // Textured floor/ceiling casting.
+        if self.textured && (self.floor_texture.is_some() || self.ceiling_texture.is_some()) {
+            self.cast_floor_ceiling(renderer, width, height);
+        }
+
+        // Cast rays (deterministic fixed-point DDA when enabled).
+        if self.deterministic {
+            self.cast_rays_fixed(map, map_width, map_height, width);
+        } else {
+            self.cast_rays(map, map_width, map_height, width);
+        }
+
+        // Draw wall stripes
         for x in 0..width {
             let distance = self.distances[x as usize];
             let side = self.hit_sides[x as usize];
-            
+            let cell = self.hit_cells[x as usize];
+
             if distance > 0.0 && distance < self.config.max_distance {
                 let line_height = ((height as f32 / distance) * self.config.wall_height) as i32;
                 let draw_start = (-line_height / 2 + height as i32 / 2).max(0);
                 let draw_end = (line_height / 2 + height as i32 / 2).min(height as i32 - 1);
-                
-                // Color based on side (darker for y-side)
-                let color = if side == 0 {
-                    0xFFCC0000 // Red for x-side
+
+                // Textured stripe when enabled and a texture exists for the cell.
+                if let (true, Some(tex)) = (self.textured, self.textures.get(&cell)) {
+                    let tex_x = (self.wall_xs[x as usize] * tex.width as f32) as u32;
+                    let tex_step = tex.height as f32 / line_height.max(1) as f32;
+                    // v-coordinate of the first (possibly clamped) drawn row.
+                    let mut tex_pos =
+                        (draw_start as f32 - height as f32 / 2.0 + line_height as f32 / 2.0) * tex_step;
+                    for y in draw_start..=draw_end {
+                        let tex_y = (tex_pos as u32).min(tex.height.saturating_sub(1));
+                        tex_pos += tex_step;
+                        let mut color = tex.sample(tex_x, tex_y);
+                        if side == 1 {
+                            color = darken(color);
+                        }
+                        renderer.buffer[(y as u32 * width + x) as usize] = color;
+                    }
                 } else {
-                    0xFF880000 // Darker red for y-side
-                };
-                
-                for y in draw_start..=draw_end {
-                    renderer.buffer[(y as u32 * width + x) as usize] = color;
+                    // Flat-shaded fallback (darker for y-side).
+                    let color = if side == 0 { 0xFFCC0000 } else { 0xFF880000 };
+                    for y in draw_start..=draw_end {
+                        renderer.buffer[(y as u32 * width + x) as usize] = color;
+                    }
                 }
             }
         }
     }
-    
+
+    /// Texture the floor and ceiling row-by-row below/above the horizon by
+    /// interpolating world positions between the leftmost and rightmost rays.
+    fn cast_floor_ceiling(&self, renderer: &mut Renderer, width: u32, height: u32) {
+        let ray_dir_x0 = self.direction.x - self.plane.x;
+        let ray_dir_y0 = self.direction.y - self.plane.y;
+        let ray_dir_x1 = self.direction.x + self.plane.x;
+        let ray_dir_y1 = self.direction.y + self.plane.y;
+
+        for y in (height / 2 + 1)..height {
+            // World distance of the floor row at screen-y.
+            let row_distance = height as f32 / (2.0 * y as f32 - height as f32);
+
+            let step_x = row_distance * (ray_dir_x1 - ray_dir_x0) / width as f32;
+            let step_y = row_distance * (ray_dir_y1 - ray_dir_y0) / width as f32;
+            let mut floor_x = self.position.x + row_distance * ray_dir_x0;
+            let mut floor_y = self.position.y + row_distance * ray_dir_y0;
+
+            // Ceiling is the mirror row across the horizon.
+            let ceil_y = height - y - 1;
+
+            for x in 0..width {
+                let tx_f = floor_x - floor_x.floor();
+                let ty_f = floor_y - floor_y.floor();
+                floor_x += step_x;
+                floor_y += step_y;
+
+                if let Some(tex) = &self.floor_texture {
+                    let tx = (tx_f * tex.width as f32) as u32;
+                    let ty = (ty_f * tex.height as f32) as u32;
+                    renderer.buffer[(y * width + x) as usize] = tex.sample(tx, ty);
+                }
+                if let Some(tex) = &self.ceiling_texture {
+                    let tx = (tx_f * tex.width as f32) as u32;
+                    let ty = (ty_f * tex.height as f32) as u32;
+                    renderer.buffer[(ceil_y * width + x) as usize] = tex.sample(tx, ty);
+                }
+            }
+        }
+    }
+
     /// Cast rays using DDA algorithm
     fn cast_rays(&mut self, map: &[u8], map_width: u32, map_height: u32, screen_width: u32) {
         for x in 0..screen_width {
@@ -155,16 +280,127 @@ impl Raycaster {
                 self.direction.x + self.plane.x * camera_x,
                 self.direction.y + self.plane.y * camera_x,
             );
-            
+
             // DDA algorithm
-            let (distance, side) = self.dda(ray_dir, map, map_width, map_height);
+            let (distance, side, cell) = self.dda(ray_dir, map, map_width, map_height);
             self.distances[x as usize] = distance;
             self.hit_sides[x as usize] = side;
+            self.hit_cells[x as usize] = cell;
+
+            // Exact fractional hit position along the wall, for the texture column.
+            let wall_x = if side == 0 {
+                self.position.y + distance * ray_dir.y
+            } else {
+                self.position.x + distance * ray_dir.x
+            };
+            self.wall_xs[x as usize] = wall_x - wall_x.floor();
         }
     }
-    
-    /// Digital Differential Analysis for single ray
-    fn dda(&self, ray_dir: Vec2, map: &[u8], map_width: u32, map_height: u32) -> (f32, u8) {
+
+    /// Deterministic counterpart to [`cast_rays`](Self::cast_rays): the camera
+    /// pose and every ray are evaluated in 16.16 fixed point so the column
+    /// distances never depend on the host FPU.
+    fn cast_rays_fixed(&mut self, map: &[u8], map_width: u32, map_height: u32, screen_width: u32) {
+        let pos = FixedVec2::from_vec2(self.position);
+        let dir = FixedVec2::from_vec2(self.direction);
+        let plane = FixedVec2::from_vec2(self.plane);
+
+        for x in 0..screen_width {
+            // camera_x = 2x/width - 1, in fixed point.
+            let camera_x = FixedPoint::from_int(2 * x as i32)
+                .div_full(FixedPoint::from_int(screen_width as i32))
+                - FixedPoint::ONE;
+            let ray_dir = FixedVec2::new(
+                dir.x + plane.x.mul_full(camera_x),
+                dir.y + plane.y.mul_full(camera_x),
+            );
+
+            let (distance, side, cell) = self.dda_fixed(pos, ray_dir, map, map_width, map_height);
+            self.distances[x as usize] = distance.to_f32();
+            self.hit_sides[x as usize] = side;
+            self.hit_cells[x as usize] = cell;
+
+            let wall = if side == 0 {
+                pos.y + distance.mul_full(ray_dir.y)
+            } else {
+                pos.x + distance.mul_full(ray_dir.x)
+            };
+            self.wall_xs[x as usize] = (wall - wall.floor()).to_f32();
+        }
+    }
+
+    /// Fixed-point DDA for a single ray, mirroring [`dda`](Self::dda). Returns
+    /// the perpendicular hit distance, the hit side, and the map cell value.
+    fn dda_fixed(
+        &self,
+        pos: FixedVec2,
+        ray_dir: FixedVec2,
+        map: &[u8],
+        map_width: u32,
+        map_height: u32,
+    ) -> (FixedPoint, u8, u8) {
+        let far = FixedPoint::from_raw(i32::MAX);
+        let max_distance = FixedPoint::from_f32(self.config.max_distance);
+
+        let mut map_x = pos.x.to_int();
+        let mut map_y = pos.y.to_int();
+
+        let delta_dist_x = if ray_dir.x == FixedPoint::ZERO {
+            far
+        } else {
+            FixedPoint::ONE.div_full(ray_dir.x).abs()
+        };
+        let delta_dist_y = if ray_dir.y == FixedPoint::ZERO {
+            far
+        } else {
+            FixedPoint::ONE.div_full(ray_dir.y).abs()
+        };
+
+        let (step_x, mut side_dist_x) = if ray_dir.x < FixedPoint::ZERO {
+            (-1, (pos.x - FixedPoint::from_int(map_x)).mul_full(delta_dist_x))
+        } else {
+            (1, (FixedPoint::from_int(map_x) + FixedPoint::ONE - pos.x).mul_full(delta_dist_x))
+        };
+        let (step_y, mut side_dist_y) = if ray_dir.y < FixedPoint::ZERO {
+            (-1, (pos.y - FixedPoint::from_int(map_y)).mul_full(delta_dist_y))
+        } else {
+            (1, (FixedPoint::from_int(map_y) + FixedPoint::ONE - pos.y).mul_full(delta_dist_y))
+        };
+
+        let mut side = 0u8;
+        for _ in 0..64 {
+            if side_dist_x < side_dist_y {
+                side_dist_x = side_dist_x + delta_dist_x;
+                map_x += step_x;
+                side = 0;
+            } else {
+                side_dist_y = side_dist_y + delta_dist_y;
+                map_y += step_y;
+                side = 1;
+            }
+
+            if map_x < 0 || map_x >= map_width as i32 || map_y < 0 || map_y >= map_height as i32 {
+                return (max_distance, side, 0);
+            }
+
+            let cell = map[(map_y as u32 * map_width + map_x as u32) as usize];
+            if cell > 0 {
+                let distance = if side == 0 {
+                    side_dist_x - delta_dist_x
+                } else {
+                    side_dist_y - delta_dist_y
+                };
+                return (distance, side, cell);
+            }
+        }
+
+        (max_distance, side, 0)
+    }
+
+    /// Digital Differential Analysis for single ray. Returns the perpendicular
+    /// hit distance, the hit side (`0` = x-side, `1` = y-side), and the map
+    /// cell value that was hit.
+    fn dda(&self, ray_dir: Vec2, map: &[u8], map_width: u32, map_height: u32) -> (f32, u8, u8) {
         let mut map_x = self.position.x as i32;
         let mut map_y = self.position.y as i32;
         
@@ -198,22 +434,23 @@ impl Raycaster {
             }
             
             // Check bounds
-            if map_x < 0 || map_x >= map_width as i32 || 
+            if map_x < 0 || map_x >= map_width as i32 ||
                map_y < 0 || map_y >= map_height as i32 {
-                return (self.config.max_distance, side);
+                return (self.config.max_distance, side, 0);
             }
-            
+
             // Check hit
-            if map[(map_y as u32 * map_width + map_x as u32) as usize] > 0 {
+            let cell = map[(map_y as u32 * map_width + map_x as u32) as usize];
+            if cell > 0 {
                 let distance = if side == 0 {
                     side_dist_x - delta_dist_x
                 } else {
                     side_dist_y - delta_dist_y
                 };
-                return (distance, side);
+                return (distance, side, cell);
             }
         }
-        
-        (self.config.max_distance, side)
+
+        (self.config.max_distance, side, 0)
     }
 }
@@ -1,6 +1,7 @@
 //! Software rendering utilities
 
 use super::{Color, Renderer};
+use crate::math::Vec2;
 
 /// Blend two colors with alpha
 #[inline]
@@ -61,6 +62,50 @@ pub fn lerp_color(a: Color, b: Color, t: f32) -> Color {
     rgb(r, g, b)
 }
 
+/// Fill a convex quad (4 corners, in order around the perimeter) span-by-span.
+///
+/// Used by the tracer/beam primitives. When `blend` is set each pixel is
+/// composited through [`Renderer::blend_pixel`]; otherwise it is overwritten.
+pub fn fill_quad(renderer: &mut Renderer, quad: &[Vec2; 4], color: Color, blend: bool) {
+    let mut y_min = f32::INFINITY;
+    let mut y_max = f32::NEG_INFINITY;
+    for p in quad {
+        y_min = y_min.min(p.y);
+        y_max = y_max.max(p.y);
+    }
+
+    let y0 = (y_min.floor() as i32).max(0);
+    let y1 = (y_max.ceil() as i32).min(renderer.height as i32 - 1);
+
+    for y in y0..=y1 {
+        let yc = y as f32 + 0.5;
+        // Intersect the scanline with every edge; the convex hull gives a single span.
+        let mut x_lo = f32::INFINITY;
+        let mut x_hi = f32::NEG_INFINITY;
+        for i in 0..4 {
+            let a = quad[i];
+            let b = quad[(i + 1) % 4];
+            if (a.y <= yc && b.y > yc) || (b.y <= yc && a.y > yc) {
+                let t = (yc - a.y) / (b.y - a.y);
+                let x = a.x + (b.x - a.x) * t;
+                x_lo = x_lo.min(x);
+                x_hi = x_hi.max(x);
+            }
+        }
+        if x_lo > x_hi { continue; }
+
+        let xs = (x_lo.round() as i32).max(0);
+        let xe = (x_hi.round() as i32).min(renderer.width as i32 - 1);
+        for x in xs..=xe {
+            if blend {
+                renderer.blend_pixel(x, y, color);
+            } else {
+                renderer.set_pixel(x, y, color);
+            }
+        }
+    }
+}
+
 /// Horizontal line (optimized)
 pub fn hline(renderer: &mut Renderer, x0: i32, x1: i32, y: i32, color: Color) {
     if y < 0 || y >= renderer.height as i32 { return; }
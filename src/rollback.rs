@@ -0,0 +1,185 @@
+//! Rollback netcode subsystem.
+//!
+//! A GGRS/GGPO-style P2P lockstep helper built on the engine's deterministic
+//! fixed-timestep loop. Two peers run the *same* simulation; each frame they
+//! exchange inputs, predict what they have not yet received (by repeating the
+//! last known value), and advance. When an authoritative input for an earlier
+//! frame arrives and differs from the prediction, the session restores the
+//! snapshot taken at that frame and re-simulates forward to the present with
+//! the corrected inputs.
+//!
+//! The whole scheme rests on one invariant: [`Simulation::fixed_update`] must
+//! be **pure** given `(state, input)` — no wall-clock reads, no unseeded RNG,
+//! no I/O. Re-simulation reproduces identical state only if this holds, so
+//! implementors must route all time through the fixed timestep and all
+//! randomness through a seeded generator captured by `snapshot`.
+
+use std::collections::VecDeque;
+
+/// A deterministic simulation that can be snapshotted and replayed.
+pub trait Simulation {
+    /// Per-frame input (the combined inputs of every peer for one frame).
+    type Input: Clone + PartialEq;
+
+    /// Advance exactly one fixed step. Must be pure in `(state, input)`.
+    fn fixed_update(&mut self, input: &Self::Input);
+
+    /// Serialize the full simulation state to bytes.
+    fn snapshot(&self) -> Vec<u8>;
+
+    /// Restore a previously captured state. Returns `false` (leaving the
+    /// simulation untouched) if `bytes` is truncated or otherwise doesn't
+    /// match `snapshot`'s encoding, instead of panicking on a corrupt or
+    /// foreign buffer.
+    fn restore(&mut self, bytes: &[u8]) -> bool;
+}
+
+/// 64-bit FNV-1a hash, used as a cheap per-frame desync checksum.
+pub fn checksum(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash = OFFSET;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// A saved frame: the state captured *before* the step plus the input that
+/// produced it (predicted or confirmed) and a checksum of that state.
+#[derive(Clone)]
+struct SavedFrame<I> {
+    frame: u64,
+    state: Vec<u8>,
+    checksum: u64,
+    input: I,
+}
+
+/// Wraps a [`Simulation`] with a ring buffer of recent frames and the
+/// rollback/re-simulate machinery.
+pub struct Session<S: Simulation> {
+    sim: S,
+    frame: u64,
+    max_frames: usize,
+    history: VecDeque<SavedFrame<S::Input>>,
+}
+
+impl<S: Simulation> Session<S> {
+    /// Create a session keeping the last `max_frames` saved snapshots.
+    pub fn new(sim: S, max_frames: usize) -> Self {
+        Self {
+            sim,
+            frame: 0,
+            max_frames: max_frames.max(1),
+            history: VecDeque::with_capacity(max_frames),
+        }
+    }
+
+    /// The frame that will be produced by the next [`advance`](Self::advance).
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Borrow the wrapped simulation (e.g. to render it).
+    pub fn simulation(&self) -> &S {
+        &self.sim
+    }
+
+    /// Checksum of the most recently saved frame (whose input may still be a
+    /// prediction), for desync detection.
+    pub fn last_checksum(&self) -> Option<u64> {
+        self.history.back().map(|f| f.checksum)
+    }
+
+    /// The checksum recorded for a retained `frame`, if still in the window.
+    pub fn checksum_at(&self, frame: u64) -> Option<u64> {
+        self.history.iter().find(|f| f.frame == frame).map(|f| f.checksum)
+    }
+
+    /// The input recorded for a retained `frame` (predicted or confirmed).
+    pub fn input_at(&self, frame: u64) -> Option<&S::Input> {
+        self.history.iter().find(|f| f.frame == frame).map(|f| &f.input)
+    }
+
+    /// The range of frames currently retained, `[oldest, newest]`.
+    pub fn retained(&self) -> Option<(u64, u64)> {
+        match (self.history.front(), self.history.back()) {
+            (Some(front), Some(back)) => Some((front.frame, back.frame)),
+            _ => None,
+        }
+    }
+
+    /// Restore the simulation to the state saved before `frame`'s step without
+    /// replaying forward, discarding every snapshot from `frame` on. Returns
+    /// `false` if `frame` is no longer retained.
+    pub fn rollback_to(&mut self, frame: u64) -> bool {
+        let Some(index) = self.history.iter().position(|f| f.frame == frame) else {
+            return false;
+        };
+        let state = self.history[index].state.clone();
+        let restored = self.sim.restore(&state);
+        debug_assert!(restored, "restoring a snapshot this session captured itself should never fail");
+        self.frame = frame;
+        self.history.truncate(index);
+        true
+    }
+
+    /// Store the pre-step snapshot + input, then advance one fixed step.
+    pub fn advance(&mut self, input: S::Input) {
+        let state = self.sim.snapshot();
+        let frame = SavedFrame {
+            frame: self.frame,
+            checksum: checksum(&state),
+            state,
+            input: input.clone(),
+        };
+        if self.history.len() == self.max_frames {
+            self.history.pop_front();
+        }
+        self.history.push_back(frame);
+
+        self.sim.fixed_update(&input);
+        self.frame += 1;
+    }
+
+    /// Apply an authoritative input for an earlier `frame`. If it differs from
+    /// the prediction already recorded, roll back to that frame and re-simulate
+    /// forward to the present. Returns `false` if `frame` is no longer retained.
+    pub fn confirm_input(&mut self, frame: u64, input: S::Input) -> bool {
+        let Some(index) = self.history.iter().position(|f| f.frame == frame) else {
+            return false;
+        };
+        if self.history[index].input == input {
+            return true; // Prediction was correct; nothing to replay.
+        }
+
+        self.history[index].input = input;
+
+        let target = self.frame;
+        let state = self.history[index].state.clone();
+        let replay: Vec<S::Input> = self
+            .history
+            .iter()
+            .skip(index)
+            .map(|f| f.input.clone())
+            .collect();
+
+        let restored = self.sim.restore(&state);
+        debug_assert!(restored, "restoring a snapshot this session captured itself should never fail");
+        self.frame = frame;
+        self.history.truncate(index);
+        for input in replay {
+            self.advance(input);
+        }
+        debug_assert_eq!(self.frame, target);
+        true
+    }
+}
+
+/// Predict a peer's next input by repeating the last value seen from it. This
+/// is the standard GGPO prediction and is correct most frames, since inputs
+/// rarely change every frame.
+pub fn predict<I: Clone>(last: &I) -> I {
+    last.clone()
+}
@@ -0,0 +1,186 @@
+//! Optional Lua scripting layer (behind the `scripting` feature)
+//!
+//! Lets game logic and entity behavior be written in Lua rather than recompiled
+//! Rust, turning the engine into a moddable/live-tunable tool. A [`ScriptContext`]
+//! loads a script, invokes its `on_tick(tick, dt)` callback from the fixed-update
+//! step, and exposes safe bindings to spawn particles and trigger audio.
+//!
+//! Scripts run *inside* the deterministic loop, so the bindings deliberately
+//! expose no wall-clock or OS-RNG access: randomness is routed through the
+//! engine's seeded RNG via `engine.random()`. Side effects are collected into a
+//! command queue the engine drains after each tick, keeping the binding layer
+//! free of borrow-checker entanglements with live `World`/`Renderer` state.
+//!
+//! Hot-reload is supported: [`ScriptContext::maybe_reload`] re-reads the script
+//! file when its mtime changes, and should be called at a frame boundary so the
+//! running tick is never interrupted.
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use mlua::Lua;
+
+/// A deferred side effect requested by a script during a tick.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptCommand {
+    SpawnParticle { template: u16, x: f32, y: f32, dx: f32, dy: f32 },
+    PlaySound(u32),
+    PlayMusic(usize),
+}
+
+/// Errors surfaced by the scripting layer.
+#[derive(Debug)]
+pub enum ScriptError {
+    Io(std::io::Error),
+    Lua(mlua::Error),
+}
+
+impl From<std::io::Error> for ScriptError {
+    fn from(e: std::io::Error) -> Self {
+        ScriptError::Io(e)
+    }
+}
+
+impl From<mlua::Error> for ScriptError {
+    fn from(e: mlua::Error) -> Self {
+        ScriptError::Lua(e)
+    }
+}
+
+/// Shared state the Lua bindings write into.
+#[derive(Default)]
+struct Shared {
+    commands: Vec<ScriptCommand>,
+    /// Seeded engine RNG exposed to scripts as `engine.random()`.
+    rng_state: u32,
+}
+
+/// A loaded Lua script plus its safe engine bindings.
+pub struct ScriptContext {
+    lua: Lua,
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+    shared: Rc<RefCell<Shared>>,
+    seed: u32,
+}
+
+impl ScriptContext {
+    /// Create a context whose `engine.random()` draws from `seed`.
+    pub fn new(path: impl Into<PathBuf>, seed: u32) -> Result<Self, ScriptError> {
+        let shared = Rc::new(RefCell::new(Shared { commands: Vec::new(), rng_state: seed | 1 }));
+        let lua = Lua::new();
+        Self::strip_nondeterministic_globals(&lua)?;
+        Self::install_bindings(&lua, &shared)?;
+
+        let mut ctx = Self { lua, path: path.into(), last_modified: None, shared, seed };
+        ctx.reload()?;
+        Ok(ctx)
+    }
+
+    /// Remove the stdlib entry points that would break determinism: `os`
+    /// gives scripts wall-clock time, `io` gives arbitrary file access, and
+    /// `math.random`/`math.randomseed` are an unseeded RNG that would diverge
+    /// across rollback re-simulation or networked peers. Scripts must go
+    /// through `engine.random()` instead (see `install_bindings`).
+    fn strip_nondeterministic_globals(lua: &Lua) -> Result<(), ScriptError> {
+        let globals = lua.globals();
+        globals.set("os", mlua::Value::Nil)?;
+        globals.set("io", mlua::Value::Nil)?;
+        if let Ok(math) = globals.get::<mlua::Table>("math") {
+            math.set("random", mlua::Value::Nil)?;
+            math.set("randomseed", mlua::Value::Nil)?;
+        }
+        Ok(())
+    }
+
+    /// Install the `engine` table of safe, deterministic bindings.
+    fn install_bindings(lua: &Lua, shared: &Rc<RefCell<Shared>>) -> Result<(), ScriptError> {
+        let engine = lua.create_table()?;
+
+        let s = shared.clone();
+        engine.set(
+            "spawn_particle",
+            lua.create_function(move |_, (template, x, y, dx, dy): (u16, f32, f32, f32, f32)| {
+                s.borrow_mut().commands.push(ScriptCommand::SpawnParticle { template, x, y, dx, dy });
+                Ok(())
+            })?,
+        )?;
+
+        let s = shared.clone();
+        engine.set(
+            "play_sound",
+            lua.create_function(move |_, id: u32| {
+                s.borrow_mut().commands.push(ScriptCommand::PlaySound(id));
+                Ok(())
+            })?,
+        )?;
+
+        let s = shared.clone();
+        engine.set(
+            "play_music",
+            lua.create_function(move |_, id: usize| {
+                s.borrow_mut().commands.push(ScriptCommand::PlayMusic(id));
+                Ok(())
+            })?,
+        )?;
+
+        // Deterministic RNG in [0, 1); scripts must use this instead of math.random.
+        let s = shared.clone();
+        engine.set(
+            "random",
+            lua.create_function(move |_, ()| {
+                let mut shared = s.borrow_mut();
+                let mut x = shared.rng_state;
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                shared.rng_state = x;
+                Ok((x >> 8) as f64 / (1u32 << 24) as f64)
+            })?,
+        )?;
+
+        lua.globals().set("engine", engine)?;
+        Ok(())
+    }
+
+    /// Read and execute the script file, (re)defining its globals.
+    pub fn reload(&mut self) -> Result<(), ScriptError> {
+        let source = std::fs::read_to_string(&self.path)?;
+        // Reset the RNG so reloads stay reproducible from the same seed.
+        self.shared.borrow_mut().rng_state = self.seed | 1;
+        self.lua.load(&source).set_name(self.path.to_string_lossy()).exec()?;
+        self.last_modified = file_mtime(&self.path);
+        Ok(())
+    }
+
+    /// Reload the script if its file changed on disk. Call at a frame boundary.
+    pub fn maybe_reload(&mut self) -> Result<bool, ScriptError> {
+        let current = file_mtime(&self.path);
+        if current != self.last_modified {
+            self.reload()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Invoke the script's `on_tick(tick, dt)` callback, if defined.
+    pub fn on_tick(&self, tick: u64, dt: f32) -> Result<(), ScriptError> {
+        let globals = self.lua.globals();
+        if let Ok(func) = globals.get::<mlua::Function>("on_tick") {
+            func.call::<()>((tick, dt))?;
+        }
+        Ok(())
+    }
+
+    /// Drain the side effects requested by the script since the last drain.
+    pub fn drain_commands(&self) -> Vec<ScriptCommand> {
+        std::mem::take(&mut self.shared.borrow_mut().commands)
+    }
+}
+
+fn file_mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}